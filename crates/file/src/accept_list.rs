@@ -0,0 +1,83 @@
+use crate::{MimeCategory, MimeType};
+use std::fmt;
+
+/// Builds the value of an `<input type="file">`'s `accept` attribute.
+///
+/// Entries are kept in the order they were added, lowercased, and added only
+/// once. The attribute value is the list's `Display` output, which
+/// [`MimeType::matches_pattern`](enum.MimeType.html#method.matches_pattern)
+/// understands.
+///
+/// ```
+/// use gloo_file::{AcceptList, MimeCategory, MimeType};
+///
+/// let accept = AcceptList::new()
+///     .category(MimeCategory::Image)
+///     .extension("pdf")
+///     .mime(MimeType::ApplicationPdf)
+///     .to_string();
+/// assert_eq!(accept, "image/*,.pdf,application/pdf");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AcceptList {
+    entries: Vec<String>,
+}
+
+impl AcceptList {
+    /// Creates an empty list, which accepts any file.
+    pub fn new() -> AcceptList {
+        AcceptList::default()
+    }
+
+    /// Accepts files of type `mime`.
+    ///
+    /// `MimeType::Unknown` has no MIME string, so it adds nothing.
+    pub fn mime(&mut self, mime: MimeType) -> &mut AcceptList {
+        self.push(mime.as_str())
+    }
+
+    /// Accepts files with the extension `extension`, given with or without
+    /// its leading dot.
+    pub fn extension(&mut self, extension: &str) -> &mut AcceptList {
+        let extension = extension.trim().trim_start_matches('.');
+        if extension.is_empty() {
+            return self;
+        }
+        self.push(&format!(".{}", extension))
+    }
+
+    /// Accepts every type in `category`, such as `image/*` for
+    /// `MimeCategory::Image`.
+    ///
+    /// `MimeCategory::Other` has no wildcard of its own, so it adds nothing.
+    pub fn category(&mut self, category: MimeCategory) -> &mut AcceptList {
+        let pattern = match category {
+            MimeCategory::Image => "image/*",
+            MimeCategory::Audio => "audio/*",
+            MimeCategory::Video => "video/*",
+            MimeCategory::Text => "text/*",
+            MimeCategory::Application => "application/*",
+            MimeCategory::Other => return self,
+        };
+        self.push(pattern)
+    }
+
+    /// Returns `true` if nothing has been added, so any file is accepted.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn push(&mut self, entry: &str) -> &mut AcceptList {
+        let entry = entry.trim().to_ascii_lowercase();
+        if !entry.is_empty() && !self.entries.contains(&entry) {
+            self.entries.push(entry);
+        }
+        self
+    }
+}
+
+impl fmt::Display for AcceptList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.entries.join(","))
+    }
+}
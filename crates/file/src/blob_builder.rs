@@ -0,0 +1,80 @@
+use crate::{DataBlob, RawBlob, Result};
+use wasm_bindgen::JsValue;
+
+/// Assembles a [`DataBlob`](struct.DataBlob.html) out of several parts.
+///
+/// Parts can be strings, bytes or other blobs, and are concatenated in the
+/// order they were appended. The blob itself is only created once, by
+/// [`build`](#method.build).
+///
+/// ```no_run
+/// use gloo_file::BlobBuilder;
+///
+/// let mut csv = BlobBuilder::new();
+/// csv.mime_type("text/csv");
+/// for row in &["a,b", "1,2", "3,4"] {
+///     csv.append_str(row).append_str("\n");
+/// }
+///
+/// let blob = csv.build().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct BlobBuilder {
+    parts: js_sys::Array,
+    mime_type: Option<String>,
+}
+
+impl BlobBuilder {
+    /// Creates a builder with no parts.
+    pub fn new() -> BlobBuilder {
+        BlobBuilder {
+            parts: js_sys::Array::new(),
+            mime_type: None,
+        }
+    }
+
+    /// Appends `part` as UTF-8 text.
+    pub fn append_str(&mut self, part: &str) -> &mut BlobBuilder {
+        self.parts.push(&JsValue::from_str(part));
+        self
+    }
+
+    /// Appends a copy of `part`.
+    pub fn append_bytes(&mut self, part: &[u8]) -> &mut BlobBuilder {
+        self.parts.push(&js_sys::Uint8Array::from(part));
+        self
+    }
+
+    /// Appends the contents of `part`.
+    pub fn append_blob(&mut self, part: &impl RawBlob) -> &mut BlobBuilder {
+        self.parts.push(part.raw());
+        self
+    }
+
+    /// Sets the MIME type of the blob.
+    pub fn mime_type(&mut self, mime: &str) -> &mut BlobBuilder {
+        self.mime_type = Some(mime.to_string());
+        self
+    }
+
+    /// Creates the blob out of the parts appended so far.
+    ///
+    /// This fails only if the browser rejects the construction of the blob.
+    pub fn build(&self) -> Result<DataBlob> {
+        let options = web_sys::BlobPropertyBag::new();
+        if let Some(mime) = &self.mime_type {
+            options.set_type(mime);
+        }
+        // All of the `new_with_*_sequence` constructors call the same `Blob`
+        // constructor, which accepts a sequence of mixed parts.
+        let inner = web_sys::Blob::new_with_blob_sequence_and_options(&self.parts, &options)?;
+
+        Ok(DataBlob::from_raw(inner))
+    }
+}
+
+impl Default for BlobBuilder {
+    fn default() -> Self {
+        BlobBuilder::new()
+    }
+}
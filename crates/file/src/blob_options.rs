@@ -0,0 +1,46 @@
+/// Options for creating a blob, mirroring the browser's
+/// [`BlobPropertyBag`](https://developer.mozilla.org/en-US/docs/Web/API/Blob/Blob#options).
+///
+/// ```no_run
+/// use gloo_file::{BlobOptions, DataBlob, Endings};
+///
+/// let options = BlobOptions {
+///     mime_type: Some("text/plain".to_string()),
+///     endings: Some(Endings::Native),
+/// };
+/// let blob = DataBlob::new_with_options("one\ntwo\n", &options).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BlobOptions {
+    /// The MIME type of the blob.
+    pub mime_type: Option<String>,
+    /// How line endings in text parts are written. Defaults to
+    /// `Endings::Transparent`.
+    pub endings: Option<Endings>,
+}
+
+impl BlobOptions {
+    pub(crate) fn to_raw(&self) -> web_sys::BlobPropertyBag {
+        let options = web_sys::BlobPropertyBag::new();
+        if let Some(mime) = &self.mime_type {
+            options.set_type(mime);
+        }
+        if let Some(endings) = self.endings {
+            options.set_endings(match endings {
+                Endings::Transparent => web_sys::EndingTypes::Transparent,
+                Endings::Native => web_sys::EndingTypes::Native,
+            });
+        }
+        options
+    }
+}
+
+/// How line endings in the text parts of a blob are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endings {
+    /// Line endings are kept as they are.
+    Transparent,
+    /// `\n` is converted to the line ending of the user's platform, e.g.
+    /// `\r\n` on Windows.
+    Native,
+}
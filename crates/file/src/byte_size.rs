@@ -0,0 +1,45 @@
+use std::fmt;
+
+/// A size in bytes, which displays in human units, e.g. `12.3 KB`.
+///
+/// Sizes use powers of 1024, and are rounded to one decimal from a kilobyte
+/// up.
+///
+/// ```
+/// use gloo_file::ByteSize;
+///
+/// assert_eq!(ByteSize(512).to_string(), "512 B");
+/// assert_eq!(ByteSize(12_595).to_string(), "12.3 KB");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> ByteSize {
+        ByteSize(bytes)
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 5] = ["KB", "MB", "GB", "TB", "PB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Formats a size in bytes for people, e.g. `"12.3 KB"`.
+///
+/// This is a shorthand for displaying a [`ByteSize`](struct.ByteSize.html).
+pub fn humanize_bytes(bytes: u64) -> String {
+    ByteSize(bytes).to_string()
+}
@@ -0,0 +1,54 @@
+use crate::{File, Result};
+use futures::channel::oneshot;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Encodes the contents of `canvas` as an image file called `name`, with
+/// [`HTMLCanvasElement.toBlob()`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLCanvasElement/toBlob).
+///
+/// `quality`, between `0.0` and `1.0`, applies to lossy formats such as
+/// `image/jpeg`. Browsers encode types they don't support as `image/png`.
+/// This fails if the canvas can't be encoded at all, for example because it
+/// has no pixels.
+///
+/// ```no_run
+/// use gloo_file::file_from_canvas;
+///
+/// # async fn example(canvas: web_sys::HtmlCanvasElement) -> gloo_file::Result<()> {
+/// let photo = file_from_canvas(&canvas, "image/jpeg", 0.9, "photo.jpg").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn file_from_canvas(
+    canvas: &web_sys::HtmlCanvasElement,
+    mime: &str,
+    quality: f64,
+    name: &str,
+) -> Result<File> {
+    let (sender, receiver) = oneshot::channel();
+    // The callback frees itself once called, so it doesn't dangle if this
+    // future is dropped before the canvas is encoded.
+    let callback = Closure::once_into_js(move |blob: JsValue| {
+        let _ = sender.send(blob);
+    });
+    canvas.to_blob_with_type_and_encoder_options(
+        callback.unchecked_ref(),
+        mime,
+        &JsValue::from_f64(quality),
+    )?;
+
+    let blob = receiver
+        .await
+        .ok()
+        .and_then(|blob| blob.dyn_into::<web_sys::Blob>().ok())
+        .ok_or_else(|| JsValue::from_str("the canvas could not be encoded"))?;
+
+    let options = web_sys::FilePropertyBag::new();
+    options.set_type(&blob.type_());
+    let inner = web_sys::File::new_with_blob_sequence_and_options(
+        &js_sys::Array::of1(&blob),
+        name,
+        &options,
+    )?;
+    Ok(File::from_raw(inner))
+}
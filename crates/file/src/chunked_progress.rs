@@ -0,0 +1,106 @@
+/// Tracks the progress of a file handled in chunks, such as a resumable
+/// upload of the chunks from [`File::chunks`](struct.File.html#method.chunks).
+///
+/// Chunks can be marked complete in any order, and each counts towards the
+/// progress by its own size, so a short last chunk counts for less.
+///
+/// ```
+/// use gloo_file::ChunkedReadProgress;
+///
+/// let mut progress = ChunkedReadProgress::new(10, 4);
+/// progress.complete(2);
+/// assert_eq!(progress.completed_bytes(), 2);
+/// progress.complete(0);
+/// assert_eq!(progress.fraction(), 0.6);
+/// assert_eq!(progress.remaining().collect::<Vec<_>>(), [1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkedReadProgress {
+    size: u64,
+    chunk_size: u64,
+    completed: Vec<bool>,
+    completed_bytes: u64,
+}
+
+impl ChunkedReadProgress {
+    /// Tracks a file of `size` bytes, split into chunks of `chunk_size` bytes
+    /// like [`File::chunks`](struct.File.html#method.chunks) splits it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn new(size: u64, chunk_size: usize) -> ChunkedReadProgress {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        let chunk_size = chunk_size as u64;
+        ChunkedReadProgress {
+            size,
+            chunk_size,
+            completed: vec![false; size.div_ceil(chunk_size) as usize],
+            completed_bytes: 0,
+        }
+    }
+
+    /// The number of chunks the file is split into.
+    pub fn chunk_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Marks the chunk at `index` complete, returning `false` if it already
+    /// was.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`chunk_count`](#method.chunk_count).
+    pub fn complete(&mut self, index: usize) -> bool {
+        if self.completed[index] {
+            return false;
+        }
+        self.completed[index] = true;
+        self.completed_bytes += self.chunk_len(index);
+        true
+    }
+
+    /// Returns `true` if the chunk at `index` has been marked complete.
+    ///
+    /// This is `false` for an `index` out of bounds.
+    pub fn is_complete(&self, index: usize) -> bool {
+        self.completed.get(index).copied().unwrap_or(false)
+    }
+
+    /// Returns `true` once every chunk has been marked complete.
+    pub fn is_done(&self) -> bool {
+        self.completed_bytes == self.size
+    }
+
+    /// The combined size of the completed chunks, in bytes.
+    pub fn completed_bytes(&self) -> u64 {
+        self.completed_bytes
+    }
+
+    /// The fraction of the file's bytes in completed chunks, between `0.0`
+    /// and `1.0`.
+    ///
+    /// An empty file has no chunks, so it is always `1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.size == 0 {
+            return 1.0;
+        }
+        self.completed_bytes as f64 / self.size as f64
+    }
+
+    /// Iterates over the indexes of the chunks not yet completed, in order,
+    /// for resuming.
+    pub fn remaining(&self) -> impl Iterator<Item = usize> + '_ {
+        self.completed
+            .iter()
+            .enumerate()
+            .filter(|(_, &completed)| !completed)
+            .map(|(index, _)| index)
+    }
+
+    fn chunk_len(&self, index: usize) -> u64 {
+        let start = index as u64 * self.chunk_size;
+        self.size.min(start + self.chunk_size) - start
+    }
+}
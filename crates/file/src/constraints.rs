@@ -0,0 +1,118 @@
+use crate::{Blob, ByteSize, File, MimeType};
+use std::error::Error;
+use std::fmt;
+
+/// Limits on the files a user may select, checked by
+/// [`FileList::validate`](struct.FileList.html#method.validate).
+///
+/// Every limit is optional, and the default allows anything.
+#[derive(Debug, Clone, Default)]
+pub struct FileConstraints {
+    /// The largest size in bytes a file may be.
+    pub max_size: Option<u64>,
+    /// The types of file allowed, in the syntax of an `<input type="file">`'s
+    /// `accept` attribute, e.g. `"image/*, .pdf"`.
+    ///
+    /// A file is allowed if its type matches one of the MIME types or
+    /// wildcards, or its extension is one of the `.ext` entries.
+    pub accept: Option<String>,
+    /// The most files which may be selected at once.
+    pub max_files: Option<usize>,
+}
+
+impl FileConstraints {
+    /// Checks one file against the size and type limits, pushing each
+    /// violation onto `errors`.
+    pub(crate) fn check(&self, file: &File, errors: &mut Vec<FileValidationError>) {
+        if let Some(max) = self.max_size {
+            if file.size() > max {
+                errors.push(FileValidationError::TooLarge {
+                    name: file.name().to_string(),
+                    size: file.size(),
+                    max,
+                });
+            }
+        }
+        if let Some(accept) = &self.accept {
+            if !accepts(accept, file) {
+                errors.push(FileValidationError::WrongType {
+                    name: file.name().to_string(),
+                    mime_type: file.mime_type(),
+                });
+            }
+        }
+    }
+}
+
+/// Returns `true` if `file` matches the `accept` attribute `accept`.
+fn accepts(accept: &str, file: &File) -> bool {
+    if file.mime_type().matches_pattern(accept) {
+        return true;
+    }
+    let extension = match file.extension() {
+        Some(extension) => extension,
+        None => return false,
+    };
+    accept
+        .split(',')
+        .filter_map(|entry| entry.trim().strip_prefix('.'))
+        .any(|entry| entry.eq_ignore_ascii_case(&extension))
+}
+
+/// A way a selection of files breaks its
+/// [`FileConstraints`](struct.FileConstraints.html).
+#[derive(Debug, PartialEq, Eq)]
+pub enum FileValidationError {
+    /// The file is larger than `max_size`.
+    TooLarge {
+        /// The file's name.
+        name: String,
+        /// The file's size in bytes.
+        size: u64,
+        /// The largest size allowed.
+        max: u64,
+    },
+
+    /// The file's type isn't one of those `accept`ed.
+    WrongType {
+        /// The file's name.
+        name: String,
+        /// The file's type.
+        mime_type: MimeType,
+    },
+
+    /// More files were selected than `max_files`.
+    TooManyFiles {
+        /// The number of files selected.
+        count: usize,
+        /// The most files allowed.
+        max: usize,
+    },
+}
+
+impl fmt::Display for FileValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileValidationError::TooLarge { name, size, max } => write!(
+                f,
+                "{} is {}, larger than the limit of {}",
+                name,
+                ByteSize(*size),
+                ByteSize(*max)
+            ),
+            FileValidationError::WrongType { name, mime_type } => match mime_type {
+                MimeType::Unknown => {
+                    write!(f, "{} is of an unknown type, which isn't allowed", name)
+                }
+                _ => write!(f, "{} is of type {}, which isn't allowed", name, mime_type),
+            },
+            FileValidationError::TooManyFiles { count, max } => write!(
+                f,
+                "{} files were selected, but at most {} are allowed",
+                count, max
+            ),
+        }
+    }
+}
+
+impl Error for FileValidationError {}
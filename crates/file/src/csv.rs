@@ -0,0 +1,106 @@
+use crate::{File, FileReadError};
+use futures::stream::{self, Stream, StreamExt};
+use std::mem;
+
+impl File {
+    /// Reads the file as CSV, as a stream of records of fields, reading it a
+    /// line at a time with [`lines`](#method.lines).
+    ///
+    /// Fields are separated by commas, and may be quoted with `"` to contain
+    /// commas, newlines, or quotes written as `""`. When `has_header` is
+    /// `true` the first record is skipped. Blank lines are skipped too, and
+    /// a quoted field still open at the end of the file runs to the end. The
+    /// stream ends after the first error.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let mut records = Box::pin(file.csv_records(true));
+    /// while let Some(record) = records.next().await {
+    ///     let record = record?;
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn csv_records(
+        &self,
+        has_header: bool,
+    ) -> impl Stream<Item = Result<Vec<String>, FileReadError>> {
+        let lines = Box::pin(self.lines());
+
+        stream::unfold(
+            Some((lines, RecordParser::default(), has_header)),
+            |state| async move {
+                let (mut lines, mut parser, mut header) = state?;
+                loop {
+                    let record = match lines.next().await {
+                        Some(Ok(line)) => parser.push_line(&line),
+                        Some(Err(error)) => return Some((Err(error), None)),
+                        None => match parser.finish() {
+                            Some(record) if !header => return Some((Ok(record), None)),
+                            _ => return None,
+                        },
+                    };
+                    match record {
+                        Some(_) if header => header = false,
+                        Some(record) => return Some((Ok(record), Some((lines, parser, header)))),
+                        None => {}
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// Splits lines of CSV into records, holding on to a record while one of its
+/// quoted fields spans several lines.
+#[derive(Default)]
+struct RecordParser {
+    fields: Vec<String>,
+    field: String,
+    /// Whether the last line ended inside a quoted field.
+    quoted: bool,
+}
+
+impl RecordParser {
+    /// Parses the next line, returning the record it completes, if any.
+    fn push_line(&mut self, line: &str) -> Option<Vec<String>> {
+        if line.is_empty() && !self.quoted {
+            return None;
+        }
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (self.quoted, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    self.field.push('"');
+                }
+                (true, '"') => self.quoted = false,
+                (false, '"') => self.quoted = true,
+                (false, ',') => self.fields.push(mem::take(&mut self.field)),
+                (_, c) => self.field.push(c),
+            }
+        }
+
+        if self.quoted {
+            self.field.push('\n');
+            None
+        } else {
+            self.fields.push(mem::take(&mut self.field));
+            Some(mem::take(&mut self.fields))
+        }
+    }
+
+    /// Returns the record left open by an unterminated quoted field, if any.
+    fn finish(&mut self) -> Option<Vec<String>> {
+        if !self.quoted {
+            return None;
+        }
+        self.field.pop();
+        self.fields.push(mem::take(&mut self.field));
+        Some(mem::take(&mut self.fields))
+    }
+}
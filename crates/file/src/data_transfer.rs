@@ -0,0 +1,26 @@
+use crate::{Error, File, Result};
+
+/// Adds `file` to `transfer`, such as the `DataTransfer` of a `dragstart`
+/// event, so that it can be dropped elsewhere as a file.
+///
+/// Browsers only let files be added while the transfer is writable, which
+/// for a drag is during `dragstart`. Adding at any other time fails with
+/// `Error::Unsupported`, rather than silently doing nothing as the browser
+/// does.
+///
+/// ```no_run
+/// use gloo_file::{add_to_data_transfer, File};
+///
+/// fn on_drag_start(event: &web_sys::DragEvent, file: &File) -> gloo_file::Result<()> {
+///     if let Some(transfer) = event.data_transfer() {
+///         add_to_data_transfer(&transfer, file)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn add_to_data_transfer(transfer: &web_sys::DataTransfer, file: &File) -> Result<()> {
+    match transfer.items().add_with_file(file.as_raw())? {
+        Some(_) => Ok(()),
+        None => Err(Error::Unsupported("adding files to this DataTransfer")),
+    }
+}
@@ -0,0 +1,40 @@
+//! Diagnostics for finding leaked object URLs, behind the
+//! `debug-leak-tracking` feature.
+//!
+//! Counting only happens in builds with debug assertions, so enabling the
+//! feature costs nothing in release builds, where the count is always `0`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static LIVE_OBJECT_URLS: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of [`ObjectUrl`](../struct.ObjectUrl.html)s which exist and so
+/// haven't been revoked, including those held by an
+/// [`ObjectUrlSet`](../struct.ObjectUrlSet.html).
+///
+/// Checking that this is back where it was after a view is torn down shows
+/// whether it leaked any URLs, for example by `mem::forget`ting them.
+///
+/// ```no_run
+/// use gloo_file::{debug, DataBlob, ObjectUrl};
+///
+/// let before = debug::live_object_urls();
+/// let url = ObjectUrl::new(&DataBlob::new("...").unwrap());
+/// drop(url);
+/// assert_eq!(debug::live_object_urls(), before);
+/// ```
+pub fn live_object_urls() -> usize {
+    LIVE_OBJECT_URLS.load(Ordering::Relaxed)
+}
+
+pub(crate) fn object_url_created() {
+    if cfg!(debug_assertions) {
+        LIVE_OBJECT_URLS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn object_url_revoked() {
+    if cfg!(debug_assertions) {
+        LIVE_OBJECT_URLS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
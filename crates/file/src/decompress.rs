@@ -0,0 +1,78 @@
+use crate::{File, FileReadError};
+use std::future::Future;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// A compression format understood by the browser's
+/// [`DecompressionStream`](https://developer.mozilla.org/en-US/docs/Web/API/DecompressionStream).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    /// gzip, as in `.gz` files.
+    Gzip,
+    /// zlib-wrapped deflate.
+    Deflate,
+    /// Raw deflate, without a zlib header.
+    DeflateRaw,
+}
+
+impl CompressionFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gzip",
+            CompressionFormat::Deflate => "deflate",
+            CompressionFormat::DeflateRaw => "deflate-raw",
+        }
+    }
+}
+
+impl File {
+    /// Decompresses the file in the browser, by piping its stream through a
+    /// `DecompressionStream`.
+    ///
+    /// This fails with a `FileReadError::JsError` if the browser doesn't
+    /// support `DecompressionStream` or `format`, or if the file isn't
+    /// validly compressed data.
+    ///
+    /// ```no_run
+    /// use gloo_file::CompressionFormat;
+    ///
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let csv = file.decompress(CompressionFormat::Gzip).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decompress(
+        &self,
+        format: CompressionFormat,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        let body = decompressed_body(self.as_raw(), format);
+
+        async move {
+            let body = body.map_err(FileReadError::JsError)?;
+            let buffer = JsFuture::from(body.array_buffer().map_err(FileReadError::JsError)?)
+                .await
+                .map_err(FileReadError::JsError)?;
+            Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+        }
+    }
+}
+
+/// Wraps the decompressed stream of `blob` in a `Response`, to collect it
+/// with `arrayBuffer()`.
+fn decompressed_body(
+    blob: &web_sys::Blob,
+    format: CompressionFormat,
+) -> Result<web_sys::Response, JsValue> {
+    // `DecompressionStream` is only in `web-sys`'s unstable APIs.
+    let constructor =
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("DecompressionStream"))?
+            .dyn_into::<js_sys::Function>()
+            .map_err(|_| JsValue::from_str("DecompressionStream is not supported"))?;
+    let decompressor = js_sys::Reflect::construct(
+        &constructor,
+        &js_sys::Array::of1(&JsValue::from_str(format.as_str())),
+    )?;
+
+    let stream = blob.stream().pipe_through(decompressor.unchecked_ref());
+    web_sys::Response::new_with_opt_readable_stream(Some(&stream))
+}
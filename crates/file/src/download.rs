@@ -0,0 +1,110 @@
+use crate::{Error, ObjectUrl, RawBlob, Result};
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Offers `blob` to the user as a download called `filename`, as if they had
+/// clicked a link to it.
+///
+/// The name is passed through [`sanitize_filename`](fn.sanitize_filename.html)
+/// first, so it can come from user input.
+///
+/// This fails with `Error::Unsupported` if there is no document to click a
+/// link in, for example in a worker.
+///
+/// ```no_run
+/// use gloo_file::{download, DataBlob};
+///
+/// let report = DataBlob::new_with_type("a,b\n1,2\n", "text/csv").unwrap();
+/// download(&report, "report.csv").unwrap();
+/// ```
+pub fn download(blob: &impl RawBlob, filename: &str) -> Result<()> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or(Error::Unsupported("the document"))?;
+    let body = document
+        .body()
+        .ok_or_else(|| JsValue::from_str("no document body to download from"))?;
+
+    let url = ObjectUrl::new(blob);
+    let anchor: web_sys::HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(&sanitize_filename(filename));
+    // Some browsers only follow links which are in the document.
+    body.append_child(&anchor)?;
+    anchor.click();
+    body.remove_child(&anchor)?;
+
+    // The click only starts the download, so revoke the URL once the browser
+    // has had a chance to resolve it.
+    Timeout::new(0, move || drop(url)).forget();
+    Ok(())
+}
+
+/// The longest name most file systems accept, in bytes.
+const MAX_FILENAME_LEN: usize = 255;
+
+/// Names Windows reserves for devices, with or without an extension.
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Makes `name` safe to save a file as on any common operating system.
+///
+/// This removes path separators, control characters and the other characters
+/// Windows doesn't allow (`<>:"|?*`), along with leading and trailing dots
+/// and spaces, so the name can't make a hidden file. A name Windows reserves
+/// for a device, such as `CON` or `lpt1.txt`, gets a leading `_`. A name
+/// longer than 255 bytes is shortened, keeping its extension if that is
+/// under 32 bytes. If nothing is left, the name is `download`.
+///
+/// ```
+/// use gloo_file::sanitize_filename;
+///
+/// assert_eq!(sanitize_filename("../reports/q1?.csv"), "reportsq1.csv");
+/// assert_eq!(sanitize_filename("nul.txt"), "_nul.txt");
+/// ```
+pub fn sanitize_filename(name: &str) -> String {
+    let mut name: String = name
+        .chars()
+        .filter(|c| !c.is_control() && !"/\\<>:\"|?*".contains(*c))
+        .collect();
+    name.truncate(name.trim_end_matches(['.', ' ']).len());
+    // A leading dot would make a hidden file.
+    let name = name.trim_start_matches(['.', ' ']);
+    if name.is_empty() {
+        return "download".to_string();
+    }
+
+    // Shortening can end the stem on a reserved name, so that is checked
+    // after, and the prefix can need another cut.
+    let name = truncate_filename(name.to_string());
+    let stem = name.split('.').next().unwrap_or(&name);
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem.trim_end()))
+    {
+        truncate_filename(format!("_{}", name))
+    } else {
+        name
+    }
+}
+
+/// Shortens `name` to `MAX_FILENAME_LEN` bytes, keeping a short extension.
+fn truncate_filename(mut name: String) -> String {
+    if name.len() > MAX_FILENAME_LEN {
+        let extension = match name.rfind('.') {
+            Some(dot) if name.len() - dot <= MAX_FILENAME_LEN / 8 => name.split_off(dot),
+            _ => String::new(),
+        };
+        let mut end = MAX_FILENAME_LEN - extension.len();
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        name.truncate(end);
+        name.truncate(name.trim_end_matches(['.', ' ']).len());
+        name.push_str(&extension);
+    }
+
+    name
+}
@@ -0,0 +1,204 @@
+use std::error::Error as StdError;
+use std::fmt;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// A `Result` whose error defaults to this crate's [`Error`](enum.Error.html).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The reasons an operation of this crate can fail, outside of the reads
+/// themselves.
+///
+/// Reads resolve to the more specific [`FileReadError`](enum.FileReadError.html),
+/// which converts into this. This converts into a `JsValue`, for returning
+/// from `#[wasm_bindgen]` functions.
+#[derive(Debug)]
+pub enum Error {
+    /// A read failed.
+    Read(FileReadError),
+
+    /// The environment lacks an API this needs, such as the document in a
+    /// worker. This names the missing API.
+    Unsupported(&'static str),
+
+    /// The browser threw an exception, for example when refusing to construct
+    /// a blob.
+    Js(JsValue),
+}
+
+impl Error {
+    /// Converts the error for a read which couldn't be started.
+    pub(crate) fn into_read_error(self) -> FileReadError {
+        match self {
+            Error::Read(error) => error,
+            Error::Js(error) => FileReadError::JsError(error),
+            other => FileReadError::JsError(JsValue::from_str(&other.to_string())),
+        }
+    }
+}
+
+impl From<JsValue> for Error {
+    fn from(error: JsValue) -> Error {
+        Error::Js(error)
+    }
+}
+
+impl From<FileReadError> for Error {
+    fn from(error: FileReadError) -> Error {
+        Error::Read(error)
+    }
+}
+
+/// Exceptions convert back as they were thrown, and other errors into a
+/// JavaScript `Error` with the same message.
+impl From<Error> for JsValue {
+    fn from(error: Error) -> JsValue {
+        match error {
+            Error::Read(error) => error.into(),
+            Error::Js(error) => error,
+            other => js_sys::Error::new(&other.to_string()).into(),
+        }
+    }
+}
+
+/// Exceptions convert back as they were thrown, and other errors into a
+/// JavaScript `Error` with the same message. Errors the browser has a
+/// `DOMException` name for, like `FileReadError::Aborted`, take that name,
+/// here `"AbortError"`, so JavaScript can tell them apart just as it would
+/// the browser's own.
+impl From<FileReadError> for JsValue {
+    fn from(error: FileReadError) -> JsValue {
+        let name = match &error {
+            FileReadError::JsError(_) => None,
+            FileReadError::NotReadable => Some("NotReadableError"),
+            FileReadError::Aborted => Some("AbortError"),
+            FileReadError::Security => Some("SecurityError"),
+            FileReadError::TimedOut => Some("TimeoutError"),
+            FileReadError::InvalidImage => Some("EncodingError"),
+            _ => None,
+        };
+        if let FileReadError::JsError(error) = error {
+            return error;
+        }
+
+        let exception = js_sys::Error::new(&error.to_string());
+        if let Some(name) = name {
+            exception.set_name(name);
+        }
+        exception.into()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Read(error) => error.fmt(f),
+            Error::Unsupported(api) => write!(f, "this environment does not support {}", api),
+            Error::Js(error) => write!(f, "the browser threw an exception: {:?}", error),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Read(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The reasons a read can fail.
+#[derive(Debug)]
+pub enum FileReadError {
+    /// The file could not be read, for example because it was moved or its
+    /// permissions changed after it was selected.
+    NotReadable,
+
+    /// The read was aborted before it finished.
+    Aborted,
+
+    /// The browser refused the read for security reasons.
+    Security,
+
+    /// The blob is larger than the reader's size limit, so it wasn't read.
+    TooLarge,
+
+    /// The file couldn't be decoded as an image.
+    InvalidImage,
+
+    /// The read didn't finish within the reader's timeout, and was aborted.
+    TimedOut,
+
+    /// The file isn't valid JSON for the type it was read as.
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+
+    /// The file isn't a valid zip archive, or uses a compression method
+    /// other than stored or deflated.
+    #[cfg(feature = "zip")]
+    Zip(zip::result::ZipError),
+
+    /// The caller's decryptor rejected the file's contents, in
+    /// [`File::read_decrypted`](struct.File.html#method.read_decrypted).
+    Decrypt(Box<dyn StdError>),
+
+    /// Any other error reported by the browser.
+    ///
+    /// When a reader fails with an exception this crate doesn't map, this is
+    /// the `DOMException` itself, which can be converted with
+    /// `dyn_into::<web_sys::DomException>()`. Errors from outside the reader,
+    /// such as failing to create it, can be any JavaScript value.
+    JsError(JsValue),
+}
+
+impl FileReadError {
+    /// Maps the error a `web_sys::FileReader` reported to a `FileReadError`.
+    pub(crate) fn from_js(error: JsValue) -> FileReadError {
+        let name = error
+            .dyn_ref::<web_sys::DomException>()
+            .map(|exception| exception.name());
+
+        match name.as_deref() {
+            Some("NotReadableError") | Some("NotFoundError") => FileReadError::NotReadable,
+            Some("AbortError") => FileReadError::Aborted,
+            Some("SecurityError") => FileReadError::Security,
+            _ => FileReadError::JsError(error),
+        }
+    }
+}
+
+impl fmt::Display for FileReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileReadError::NotReadable => f.write_str("the file could not be read"),
+            FileReadError::Aborted => f.write_str("the read was aborted"),
+            FileReadError::Security => f.write_str("the read was refused for security reasons"),
+            FileReadError::TooLarge => f.write_str("the blob is too large to read"),
+            FileReadError::InvalidImage => f.write_str("the file is not a valid image"),
+            FileReadError::TimedOut => f.write_str("the read timed out"),
+            #[cfg(feature = "serde")]
+            FileReadError::Json(error) => write!(f, "the file is not valid JSON: {}", error),
+            #[cfg(feature = "zip")]
+            FileReadError::Zip(error) => {
+                write!(f, "the file is not a valid zip archive: {}", error)
+            }
+            FileReadError::Decrypt(error) => {
+                write!(f, "the file could not be decrypted: {}", error)
+            }
+            FileReadError::JsError(error) => write!(f, "the read failed: {:?}", error),
+        }
+    }
+}
+
+impl StdError for FileReadError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            #[cfg(feature = "serde")]
+            FileReadError::Json(error) => Some(error),
+            #[cfg(feature = "zip")]
+            FileReadError::Zip(error) => Some(error),
+            FileReadError::Decrypt(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
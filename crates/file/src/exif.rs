@@ -0,0 +1,111 @@
+use crate::{File, FileReadError};
+use std::convert::TryInto;
+use std::future::Future;
+
+/// How much of a JPEG to read looking for its EXIF data. The APP1 segment
+/// holding it is at most 64 KB, and comes before the image data, after at
+/// most a small APP0 segment.
+const HEADER_SIZE: usize = 128 * 1024;
+
+/// The TIFF tag of the orientation.
+const ORIENTATION: u16 = 0x0112;
+
+impl File {
+    /// Reads the EXIF orientation of a JPEG photo, from `1` to `8`, which
+    /// says how to rotate and flip the image to display it upright.
+    ///
+    /// Only the start of the file is read, however large the photo. This is
+    /// `None` if the file isn't a JPEG or has no valid orientation.
+    ///
+    /// ```no_run
+    /// # async fn example(photo: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let rotation = match photo.exif_orientation().await? {
+    ///     Some(3) => 180,
+    ///     Some(6) => 90,
+    ///     Some(8) => 270,
+    ///     _ => 0,
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn exif_orientation(&self) -> impl Future<Output = Result<Option<u8>, FileReadError>> {
+        let header = self.read_head(HEADER_SIZE);
+
+        async move { Ok(jpeg_orientation(&header.await?)) }
+    }
+}
+
+/// Finds the orientation in the EXIF APP1 segment of a JPEG.
+fn jpeg_orientation(jpeg: &[u8]) -> Option<u8> {
+    if !jpeg.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+
+    let mut position = 2;
+    loop {
+        if *jpeg.get(position)? != 0xff {
+            return None;
+        }
+        let marker = *jpeg.get(position + 1)?;
+        match marker {
+            // Fill bytes before a marker.
+            0xff => {
+                position += 1;
+                continue;
+            }
+            // The image data starts, or the image ends, without EXIF data.
+            0xda | 0xd9 => return None,
+            _ => {}
+        }
+
+        let length = usize::from(u16::from_be_bytes([
+            *jpeg.get(position + 2)?,
+            *jpeg.get(position + 3)?,
+        ]));
+        let segment = jpeg.get(position + 4..position + 2 + length)?;
+        if marker == 0xe1 {
+            // APP1 also holds XMP, so only stop at an EXIF segment.
+            if let Some(tiff) = segment.strip_prefix(b"Exif\0\0") {
+                return tiff_orientation(tiff);
+            }
+        }
+        position += 2 + length;
+    }
+}
+
+/// Finds the orientation tag in the first IFD of TIFF-structured data.
+fn tiff_orientation(tiff: &[u8]) -> Option<u8> {
+    let big_endian = match tiff.get(..2)? {
+        b"II" => false,
+        b"MM" => true,
+        _ => return None,
+    };
+    let u16_at = |offset: usize| {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |offset: usize| {
+        let bytes = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    if u16_at(2)? != 42 {
+        return None;
+    }
+    let ifd = u32_at(4)? as usize;
+    let entries = u16_at(ifd)?;
+    (0..usize::from(entries))
+        .map(|entry| ifd + 2 + entry * 12)
+        .find(|&entry| u16_at(entry) == Some(ORIENTATION))
+        .and_then(|entry| u16_at(entry + 8))
+        .filter(|orientation| (1..=8).contains(orientation))
+        .map(|orientation| orientation as u8)
+}
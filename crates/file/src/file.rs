@@ -0,0 +1,1194 @@
+use crate::{
+    bytes_parts, read_once, Blob, ByteSize, DataBlob, Error, FileCursor, FileReadError, FileReader,
+    MimeType, RawBlob, ReadStats, Result,
+};
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
+
+/// A file, typically one a user selected through an `<input type="file">`.
+///
+/// Cloning a `File` is cheap: the clone refers to the same underlying file.
+#[derive(Clone)]
+pub struct File {
+    inner: web_sys::File,
+    name: String,
+}
+
+impl File {
+    /// Creates a new file called `name`, containing a copy of `data` and whose
+    /// type is `mime`.
+    ///
+    /// This fails only if the browser rejects the construction of the file.
+    ///
+    /// ```no_run
+    /// use gloo_file::{File, ObjectUrl};
+    ///
+    /// let report = File::new(b"a,b\n1,2\n", "report.csv", "text/csv").unwrap();
+    /// let url = ObjectUrl::new(&report);
+    /// ```
+    pub fn new(data: &[u8], name: &str, mime: &str) -> Result<File> {
+        File::new_with_options(data, name, mime, None)
+    }
+
+    /// Creates a new file like [`new`](#method.new), but last modified at
+    /// `last_modified` milliseconds since the Unix epoch rather than now.
+    ///
+    /// This keeps the timestamp when regenerating a file, and makes files
+    /// built in tests reproducible.
+    ///
+    /// ```no_run
+    /// use gloo_file::{Blob, File};
+    ///
+    /// # fn example(original: File, contents: &[u8]) -> gloo_file::Result<()> {
+    /// let updated = File::new_with_last_modified(
+    ///     contents,
+    ///     original.name(),
+    ///     &original.raw_type(),
+    ///     original.last_modified(),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_last_modified(
+        data: &[u8],
+        name: &str,
+        mime: &str,
+        last_modified: f64,
+    ) -> Result<File> {
+        File::new_with_options(data, name, mime, Some(last_modified))
+    }
+
+    fn new_with_options(
+        data: &[u8],
+        name: &str,
+        mime: &str,
+        last_modified: Option<f64>,
+    ) -> Result<File> {
+        let options = web_sys::FilePropertyBag::new();
+        options.set_type(mime);
+        // Left unset, the browser uses the current time.
+        if let Some(last_modified) = last_modified {
+            options.set_last_modified(last_modified);
+        }
+        let inner = web_sys::File::new_with_u8_array_sequence_and_options(
+            &bytes_parts(data),
+            name,
+            &options,
+        )?;
+
+        Ok(File::from_raw(inner))
+    }
+
+    /// Returns a copy of the file called `name`, with the same contents, type
+    /// and modification time.
+    ///
+    /// The contents aren't read: the new file refers to the same data as
+    /// this one.
+    ///
+    /// ```no_run
+    /// # fn example(file: gloo_file::File) -> gloo_file::Result<()> {
+    /// let upload = file.with_name(&file.name().replace(' ', "_"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_name(&self, name: &str) -> Result<File> {
+        let options = web_sys::FilePropertyBag::new();
+        options.set_type(&self.inner.type_());
+        options.set_last_modified(self.inner.last_modified());
+        let inner = web_sys::File::new_with_blob_sequence_and_options(
+            &js_sys::Array::of1(&self.inner),
+            name,
+            &options,
+        )?;
+
+        Ok(File::from_raw(inner))
+    }
+
+    /// Returns the bytes from `start` up to, but not including, `end` as a
+    /// new file called `name`, with this file's type and modification time.
+    ///
+    /// The range is clamped like [`slice`](trait.RawBlob.html#method.slice),
+    /// and as with [`with_name`](#method.with_name) nothing is read.
+    pub fn slice_as_file(&self, start: u64, end: u64, name: &str) -> Result<File> {
+        let options = web_sys::FilePropertyBag::new();
+        options.set_type(&self.inner.type_());
+        options.set_last_modified(self.inner.last_modified());
+        let inner = web_sys::File::new_with_blob_sequence_and_options(
+            &js_sys::Array::of1(self.slice(start, end).raw()),
+            name,
+            &options,
+        )?;
+
+        Ok(File::from_raw(inner))
+    }
+
+    /// Wraps a `web_sys::File` obtained from another web API.
+    ///
+    /// This is the same as `File::from(inner)`.
+    pub fn from_raw(inner: web_sys::File) -> File {
+        File {
+            name: inner.name(),
+            inner,
+        }
+    }
+
+    /// The name of the file, without any path information.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The extension of the file's name, lowercased, e.g. `"gz"` for
+    /// `archive.tar.GZ`.
+    ///
+    /// This is `None` for names without an extension, including dotfiles
+    /// such as `.bashrc`.
+    pub fn extension(&self) -> Option<String> {
+        match self.name.rfind('.') {
+            Some(0) | None => None,
+            Some(dot) if dot + 1 == self.name.len() => None,
+            Some(dot) => Some(self.name[dot + 1..].to_lowercase()),
+        }
+    }
+
+    /// The size of the file in bytes.
+    ///
+    /// This is the same as [`Blob::size`](trait.Blob.html#tymethod.size), for
+    /// use without the trait in scope.
+    pub fn size_bytes(&self) -> u64 {
+        self.inner.size() as u64
+    }
+
+    /// The underlying `web_sys::File`.
+    pub fn as_raw(&self) -> &web_sys::File {
+        &self.inner
+    }
+
+    /// Unwraps the underlying `web_sys::File`.
+    pub fn into_raw(self) -> web_sys::File {
+        self.inner
+    }
+
+    /// The path of the file relative to the directory the user selected, for
+    /// files picked through an `<input type="file" webkitdirectory>`.
+    ///
+    /// This is `None` for files which weren't selected as part of a directory.
+    pub fn relative_path(&self) -> Option<String> {
+        // `webkitRelativePath` isn't part of `web-sys`'s `File` bindings.
+        js_sys::Reflect::get(&self.inner, &JsValue::from_str("webkitRelativePath"))
+            .ok()
+            .and_then(|path| path.as_string())
+            .filter(|path| !path.is_empty())
+    }
+
+    /// When the file was last modified, in milliseconds since the Unix epoch.
+    pub fn last_modified(&self) -> f64 {
+        self.inner.last_modified()
+    }
+
+    /// When the file was last modified, as a `SystemTime`.
+    ///
+    /// This is best-effort: browsers often report `0` (the Unix epoch) or the
+    /// creation time for files constructed in memory rather than picked by
+    /// the user.
+    pub fn last_modified_time(&self) -> SystemTime {
+        let millis = self.last_modified();
+        let offset = Duration::from_millis(millis.abs() as u64);
+        if millis >= 0.0 {
+            UNIX_EPOCH + offset
+        } else {
+            UNIX_EPOCH - offset
+        }
+    }
+
+    /// Reads the file as a UTF-8 string.
+    ///
+    /// This is a shorthand for reading the file with a new
+    /// [`FileReader`](struct.FileReader.html).
+    pub fn text(&self) -> impl Future<Output = Result<String, FileReadError>> {
+        read_once(FileReader::new().map(|reader| reader.read_as_string(self)))
+    }
+
+    /// Reads the file as text, removing the byte-order mark it starts with,
+    /// if any.
+    ///
+    /// The mark also picks the encoding: UTF-16 for a little- or big-endian
+    /// UTF-16 mark, and UTF-8 for a UTF-8 mark or none at all. The text is
+    /// decoded here rather than by the browser, so the mark never reaches
+    /// the result whatever the browser does with it. Invalid sequences are
+    /// replaced by `U+FFFD`.
+    pub fn text_no_bom(&self) -> impl Future<Output = Result<String, FileReadError>> {
+        let bytes = self.bytes();
+
+        async move { Ok(decode_with_bom(&bytes.await?)) }
+    }
+
+    /// Reads the file as a UTF-8 string, like [`text`](#method.text), for
+    /// JavaScript to `await`.
+    ///
+    /// The promise resolves to the string, or rejects with the error
+    /// converted to a `JsValue`.
+    #[cfg(feature = "promise")]
+    pub fn text_promise(&self) -> js_sys::Promise {
+        let text = self.text();
+        wasm_bindgen_futures::future_to_promise(async move { Ok(text.await?.into()) })
+    }
+
+    /// Reads the file as raw bytes for JavaScript to `await`, like
+    /// [`text_promise`](#method.text_promise).
+    ///
+    /// The promise resolves to a `Uint8Array`. The bytes are read straight
+    /// into it, without passing through wasm memory.
+    #[cfg(feature = "promise")]
+    pub fn bytes_promise(&self) -> js_sys::Promise {
+        let buffer = self.array_buffer_raw();
+        wasm_bindgen_futures::future_to_promise(async move {
+            let buffer = buffer.await?;
+            Ok(js_sys::Uint8Array::new(&buffer).into())
+        })
+    }
+
+    /// Reads the file as raw bytes.
+    ///
+    /// This is a shorthand for reading the file with a new
+    /// [`FileReader`](struct.FileReader.html).
+    pub fn bytes(&self) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        read_once(FileReader::new().map(|reader| reader.read_as_array_buffer(self)))
+    }
+
+    /// Reads the file as raw bytes, timing the read with
+    /// [`performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now).
+    ///
+    /// The read starts right away, as with [`bytes`](#method.bytes), and the
+    /// clock runs from this call until the read finishes. In the unlikely
+    /// case that there is no global `performance`, this fails with a
+    /// `FileReadError::JsError` saying so.
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let (bytes, stats) = file.bytes_with_stats().await?;
+    /// println!("read {} bytes at {:.0} B/s", stats.bytes, stats.throughput_bytes_per_sec);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bytes_with_stats(
+        &self,
+    ) -> impl Future<Output = Result<(Vec<u8>, ReadStats), FileReadError>> {
+        let clock = performance().map(|performance| {
+            let start = performance.now();
+            (performance, start)
+        });
+        let read = self.bytes();
+
+        async move {
+            // Dropping the read on failure aborts it.
+            let (performance, start) = clock.map_err(Error::into_read_error)?;
+            let bytes = read.await?;
+            let elapsed_ms = performance.now() - start;
+
+            let stats = ReadStats::new(bytes.len() as u64, elapsed_ms);
+            Ok((bytes, stats))
+        }
+    }
+
+    /// Reads the file as raw bytes and decrypts them with `decrypt`.
+    ///
+    /// The crate does no cryptography itself: `decrypt` is handed the whole
+    /// file and returns the plaintext, and an error from it becomes
+    /// [`FileReadError::Decrypt`](enum.FileReadError.html#variant.Decrypt).
+    ///
+    /// ```no_run
+    /// # fn decrypt(_: &[u8], _: &[u8]) -> Result<Vec<u8>, String> { unimplemented!() }
+    /// # async fn example(file: gloo_file::File, key: Vec<u8>) -> Result<(), gloo_file::FileReadError> {
+    /// let plaintext = file.read_decrypted(|bytes| decrypt(&key, bytes)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_decrypted<E>(
+        &self,
+        decrypt: impl Fn(&[u8]) -> Result<Vec<u8>, E>,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>>
+    where
+        E: Into<Box<dyn std::error::Error>>,
+    {
+        let bytes = self.bytes();
+        async move {
+            let bytes = bytes.await?;
+            decrypt(&bytes).map_err(|error| FileReadError::Decrypt(error.into()))
+        }
+    }
+
+    /// Reads the file as [`bytes::Bytes`](https://docs.rs/bytes), for handing
+    /// to networking crates which take `Bytes` bodies.
+    ///
+    /// The bytes are read as with [`bytes`](#method.bytes), and the `Vec`
+    /// becomes the `Bytes` without being copied again.
+    #[cfg(feature = "bytes")]
+    pub fn bytes_buf(&self) -> impl Future<Output = Result<bytes::Bytes, FileReadError>> {
+        let bytes = self.bytes();
+
+        async move { Ok(bytes::Bytes::from(bytes.await?)) }
+    }
+
+    /// Reads the file as UTF-8, decoding it in Rust rather than in the
+    /// browser.
+    ///
+    /// Invalid sequences are replaced with `U+FFFD`, as
+    /// [`String::from_utf8_lossy`](https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8_lossy)
+    /// does, which gives the same result in every browser.
+    pub fn text_lossy(&self) -> impl Future<Output = Result<String, FileReadError>> {
+        let bytes = self.bytes();
+
+        async move {
+            Ok(String::from_utf8(bytes.await?)
+                .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned()))
+        }
+    }
+
+    /// Reads the file as standard, padded base64, without the prefix of a
+    /// `data:` URL.
+    ///
+    /// The bytes are read as an `ArrayBuffer` and encoded in Rust, so the
+    /// output doesn't depend on how the browser formats data URLs.
+    pub fn base64(&self) -> impl Future<Output = Result<String, FileReadError>> {
+        let bytes = self.bytes();
+
+        async move { Ok(encode_base64(&bytes.await?)) }
+    }
+
+    /// Reads the file as a `data:` URL of type `mime`, whatever type the
+    /// browser reported for the file.
+    ///
+    /// This is for files whose reported type is missing or wrong, which
+    /// would otherwise give a URL that `<img>` or `<video>` won't render,
+    /// for example with a type found by [`sniff_mime`](#method.sniff_mime).
+    /// The bytes are encoded like [`base64`](#method.base64).
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let mime = file.sniff_mime().await?;
+    /// let url = file.data_url_with_type(mime.as_str()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn data_url_with_type(
+        &self,
+        mime: &str,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let bytes = self.bytes();
+        // Owned, so that the returned future doesn't borrow `mime`.
+        let mime = mime.to_string();
+
+        async move {
+            Ok(format!(
+                "data:{};base64,{}",
+                mime,
+                encode_base64(&bytes.await?)
+            ))
+        }
+    }
+
+    /// Reads the file's bytes onto the end of `buf`, returning how many there
+    /// were.
+    ///
+    /// The bytes are copied straight from the browser into `buf`, so reusing
+    /// one buffer across many reads saves allocating a `Vec` for each.
+    pub fn read_into<'a>(
+        &self,
+        buf: &'a mut Vec<u8>,
+    ) -> impl Future<Output = Result<usize, FileReadError>> + 'a {
+        let buffer = read_once(FileReader::new().map(|reader| {
+            reader.start_read(
+                self,
+                |reader, blob| reader.read_as_array_buffer(blob),
+                |_| js_sys::ArrayBuffer::new(0).into(),
+                |result| js_sys::Uint8Array::new(&result),
+            )
+        }));
+
+        async move {
+            let bytes = buffer.await?;
+            let start = buf.len();
+            buf.resize(start + bytes.length() as usize, 0);
+            bytes.copy_to(&mut buf[start..]);
+            Ok(buf.len() - start)
+        }
+    }
+
+    /// Reads the bytes from `start` to `end` of the file, without reading
+    /// the rest of it.
+    ///
+    /// `end` is clamped to the size of the file, and a range starting at or
+    /// beyond the end of the file reads as empty.
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let header = file.read_range(0, 8).await?;
+    /// let is_png = header == b"\x89PNG\r\n\x1a\n";
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_range(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        let end = end.min(self.size());
+        let range = self.slice(start.min(end), end);
+        read_once(FileReader::new().map(|reader| reader.read_as_array_buffer(&range)))
+    }
+
+    /// Reads the file from the start up to and including the first
+    /// `delimiter` byte, but no more than `max` bytes.
+    ///
+    /// The file is read in chunks, starting at 4 KB and doubling each time,
+    /// stopping at the chunk with the delimiter, so a short header costs one
+    /// small read however large the file. The result doesn't end with the
+    /// delimiter if none was found within `max` bytes or before the end of
+    /// the file.
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let first_line = file.read_until(b'\n', 64 * 1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_until(
+        &self,
+        delimiter: u8,
+        max: usize,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        const FIRST_CHUNK_SIZE: u64 = 4 * 1024;
+        let file = self.clone();
+        let limit = self.size().min(max as u64);
+
+        async move {
+            let mut bytes = Vec::new();
+            let mut chunk_size = FIRST_CHUNK_SIZE;
+            while (bytes.len() as u64) < limit {
+                let start = bytes.len() as u64;
+                let chunk = file
+                    .read_range(start, limit.min(start + chunk_size))
+                    .await?;
+                if let Some(end) = chunk.iter().position(|&byte| byte == delimiter) {
+                    bytes.extend_from_slice(&chunk[..=end]);
+                    break;
+                }
+                // The file shrank since it was selected.
+                if chunk.is_empty() {
+                    break;
+                }
+                bytes.extend_from_slice(&chunk);
+                chunk_size *= 2;
+            }
+            Ok(bytes)
+        }
+    }
+
+    /// Reads several ranges of the file at once, such as the header and index
+    /// of a container format.
+    ///
+    /// Each `(start, end)` range is read like [`read_range`](#method.read_range),
+    /// all concurrently, and the results are in the order of `ranges`.
+    /// Ranges may overlap and come in any order. If any read fails, the
+    /// first error is returned.
+    pub fn read_ranges(
+        &self,
+        ranges: &[(u64, u64)],
+    ) -> impl Future<Output = Result<Vec<Vec<u8>>, FileReadError>> {
+        future::try_join_all(
+            ranges
+                .iter()
+                .map(|&(start, end)| self.read_range(start, end)),
+        )
+    }
+
+    /// Reads the first `n` bytes of the file, or all of it if it is shorter.
+    ///
+    /// Only those bytes are read, so this is cheap even for huge files. It is
+    /// meant for checking headers, such as the magic numbers
+    /// [`sniff_mime`](#method.sniff_mime) looks for.
+    pub fn read_head(&self, n: usize) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        let head = self.slice(0, (n as u64).min(self.size()));
+        read_once(FileReader::new().map(|reader| reader.read_as_array_buffer(&head)))
+    }
+
+    /// Checks whether the file can still be read, by reading its first byte.
+    ///
+    /// Files can stop being readable after they were selected, for example
+    /// if they are moved or deleted, and reads of them then fail with
+    /// `FileReadError::NotReadable`. This lets that be caught before
+    /// starting a long upload. Empty files have nothing to read, so they are
+    /// always reported as readable.
+    pub fn is_readable(&self) -> impl Future<Output = bool> {
+        let head = self.read_head(1);
+
+        async move { head.await.is_ok() }
+    }
+
+    /// Reads the last `n` bytes of the file, or all of it if it is shorter.
+    ///
+    /// This is the counterpart of [`read_head`](#method.read_head), for
+    /// formats which keep their index at the end, like the central directory
+    /// of a zip archive. Only those bytes are read.
+    pub fn read_tail(&self, n: usize) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        let size = self.size();
+        let tail = self.slice(size.saturating_sub(n as u64), size);
+        read_once(FileReader::new().map(|reader| reader.read_as_array_buffer(&tail)))
+    }
+
+    /// A short string identifying the file by its name, size and modification
+    /// time, for use as a cache key without reading the file.
+    ///
+    /// The fingerprint is 16 hexadecimal digits, and is the same for a file
+    /// each time it is selected, across page loads and versions of this
+    /// crate. It is only a heuristic: like `==`, it can't tell apart files
+    /// whose contents differ but whose metadata is the same.
+    ///
+    /// ```no_run
+    /// # fn example(file: gloo_file::File) {
+    /// let mut thumbnails = std::collections::HashMap::new();
+    /// thumbnails.insert(file.fingerprint(), "data:image/png;base64,...");
+    /// # }
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        // 64-bit FNV-1a, which unlike `DefaultHasher` is stable.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let fields = [
+            self.name().as_bytes(),
+            &[0],
+            &self.size().to_le_bytes(),
+            &self.last_modified().to_bits().to_le_bytes(),
+        ];
+        for byte in fields.iter().flat_map(|field| field.iter()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("{:016x}", hash)
+    }
+
+    /// Returns `true` if `other` wraps the very same JavaScript `File` object
+    /// as this file.
+    ///
+    /// This is the strictest of the ways to compare files. `==` compares
+    /// metadata, so it can't tell apart different files with the same name,
+    /// size and modification time, and [`content_eq`](#method.content_eq)
+    /// compares contents, which means reading both. Neither is identity: a
+    /// file selected twice is two objects, even though it is the same file
+    /// on disk, while clones of one `File` are always the same object.
+    pub fn same_object(&self, other: &File) -> bool {
+        js_sys::Object::is(&self.inner, &other.inner)
+    }
+
+    /// Returns a [`FileCursor`](struct.FileCursor.html) for reading the file
+    /// bit by bit from the start, seeking as needed.
+    ///
+    /// Unlike [`into_cursor`](#method.into_cursor), this reads nothing up
+    /// front.
+    pub fn cursor(&self) -> FileCursor {
+        FileCursor::new(self.clone())
+    }
+
+    /// Returns `true` if the file has exactly the same contents as `other`.
+    ///
+    /// Files of different sizes are unequal without reading anything.
+    /// Otherwise both are read side by side, a megabyte at a time, stopping
+    /// at the first chunk that differs, so neither is held in memory whole.
+    pub fn content_eq(&self, other: &File) -> impl Future<Output = Result<bool, FileReadError>> {
+        const CHUNK_SIZE: u64 = 1024 * 1024;
+        let (a, b) = (self.clone(), other.clone());
+
+        async move {
+            let size = a.size();
+            if size != b.size() {
+                return Ok(false);
+            }
+            let mut start = 0;
+            while start < size {
+                let end = (start + CHUNK_SIZE).min(size);
+                let (x, y) =
+                    future::try_join(a.read_range(start, end), b.read_range(start, end)).await?;
+                if x != y {
+                    return Ok(false);
+                }
+                start = end;
+            }
+            Ok(true)
+        }
+    }
+
+    /// The type the browser reported for the file or, if it didn't report
+    /// one, the type its extension suggests.
+    ///
+    /// This doesn't read the file, unlike [`sniff_mime`](#method.sniff_mime).
+    /// See [`MimeType::from_extension`](enum.MimeType.html#method.from_extension)
+    /// for the extensions recognized; others leave the type `Unknown`.
+    pub fn effective_mime(&self) -> MimeType {
+        match self.mime_type() {
+            MimeType::Unknown => self
+                .extension()
+                .and_then(|extension| MimeType::from_extension(&extension))
+                .unwrap_or(MimeType::Unknown),
+            declared => declared,
+        }
+    }
+
+    /// Detects the type of the file from its first bytes, falling back to the
+    /// type the browser reported if they aren't a known magic number.
+    ///
+    /// Browsers guess the type from the file's extension, so this is more
+    /// reliable for untrusted uploads. See
+    /// [`MimeType::from_magic_bytes`](enum.MimeType.html#method.from_magic_bytes)
+    /// for the formats recognized.
+    pub fn sniff_mime(&self) -> impl Future<Output = Result<MimeType, FileReadError>> {
+        let header = self.read_head(12);
+        let declared = self.mime_type();
+
+        async move {
+            let header = header.await?;
+            Ok(MimeType::from_magic_bytes(&header).unwrap_or(declared))
+        }
+    }
+
+    /// Guesses whether the file is text, rather than binary data, from its
+    /// first 8 KB.
+    ///
+    /// A file is binary if it contains a null byte, or if more than a tenth
+    /// of its bytes are invalid UTF-8 or control characters other than
+    /// whitespace and escapes. An empty file counts as text. This is only a
+    /// guess: text in legacy encodings like Latin-1 can look binary.
+    pub fn is_probably_text(&self) -> impl Future<Output = Result<bool, FileReadError>> {
+        const HEAD_SIZE: usize = 8 * 1024;
+        let head = self.read_head(HEAD_SIZE);
+
+        async move { Ok(looks_like_text(&head.await?)) }
+    }
+
+    /// Reads the whole file and detects its type from the bytes read, like
+    /// [`sniff_mime`](#method.sniff_mime), in a single read.
+    ///
+    /// This is for when the contents are needed anyway, such as validating
+    /// an upload before storing it.
+    pub fn read_with_sniffed_type(
+        &self,
+    ) -> impl Future<Output = Result<(Vec<u8>, MimeType), FileReadError>> {
+        let bytes = self.bytes();
+        let declared = self.mime_type();
+
+        async move {
+            let bytes = bytes.await?;
+            let mime = MimeType::from_magic_bytes(&bytes).unwrap_or(declared);
+            Ok((bytes, mime))
+        }
+    }
+
+    /// Reads the file into a JavaScript `ArrayBuffer`, without copying it
+    /// into wasm memory.
+    ///
+    /// This is for handing the contents to other JavaScript APIs; use
+    /// [`bytes`](#method.bytes) to work with them in Rust.
+    pub fn array_buffer_raw(
+        &self,
+    ) -> impl Future<Output = Result<js_sys::ArrayBuffer, FileReadError>> {
+        read_once(FileReader::new().map(|reader| {
+            reader.start_read(
+                self,
+                |reader, blob| reader.read_as_array_buffer(blob),
+                |_| js_sys::ArrayBuffer::new(0).into(),
+                |result| result.unchecked_into(),
+            )
+        }))
+    }
+
+    /// Reads the file into a JavaScript `ArrayBuffer` which is ready to be
+    /// transferred to a worker or another window.
+    ///
+    /// The buffer is freshly allocated for this read and nothing else holds
+    /// on to it, so it can be listed as a transferable in `postMessage`
+    /// rather than copied. Once transferred, the buffer on this side is
+    /// detached and reads as empty.
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File, worker: web_sys::Worker) -> Result<(), wasm_bindgen::JsValue> {
+    /// let buffer = file.array_buffer_transferable().await?;
+    /// worker.post_message_with_transfer(&buffer, &js_sys::Array::of1(&buffer))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn array_buffer_transferable(
+        &self,
+    ) -> impl Future<Output = Result<js_sys::ArrayBuffer, FileReadError>> {
+        self.array_buffer_raw()
+    }
+
+    /// Computes the SHA-256 digest of the file with the browser's
+    /// [`SubtleCrypto.digest()`](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/digest).
+    ///
+    /// The file is hashed as a JavaScript `ArrayBuffer`, so its contents are
+    /// never copied into wasm memory. `SubtleCrypto` is only available in
+    /// secure contexts; elsewhere this resolves to `FileReadError::JsError`.
+    #[cfg(feature = "digest")]
+    pub fn digest_sha256(&self) -> impl Future<Output = Result<[u8; 32], FileReadError>> {
+        let buffer = self.array_buffer_raw();
+
+        async move {
+            let buffer = buffer.await?;
+            sha256(&buffer).await.map_err(FileReadError::JsError)
+        }
+    }
+
+    /// Checks whether the file's SHA-256 digest, from
+    /// [`digest_sha256`](#method.digest_sha256), is `expected`.
+    ///
+    /// The digests are compared in constant time, so how long the comparison
+    /// takes reveals nothing about how much of `expected` matched.
+    #[cfg(feature = "digest")]
+    pub fn verify_sha256(
+        &self,
+        expected: &[u8; 32],
+    ) -> impl Future<Output = Result<bool, FileReadError>> {
+        let digest = self.digest_sha256();
+        let expected = *expected;
+
+        async move { Ok(constant_time_eq(&digest.await?, &expected)) }
+    }
+
+    /// Reads the whole file into an in-memory `Cursor`, for parsers which
+    /// expect `std::io::Read` and `Seek`.
+    ///
+    /// In workers, [`FileReadSeek`](struct.FileReadSeek.html) gives the same
+    /// without holding the whole file in memory.
+    pub fn into_cursor(
+        self,
+    ) -> impl Future<Output = Result<std::io::Cursor<Vec<u8>>, FileReadError>> {
+        let bytes = self.bytes();
+
+        async move { Ok(std::io::Cursor::new(bytes.await?)) }
+    }
+
+    /// Reads the file as UTF-8 JSON, and deserializes it as a `T`.
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// #[derive(serde::Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// let config: Config = file.read_json().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn read_json<T>(&self) -> impl Future<Output = Result<T, FileReadError>>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let text = self.text();
+
+        async move { serde_json::from_str(&text.await?).map_err(FileReadError::Json) }
+    }
+
+    /// Reads the file as a zip archive, returning the name and contents of
+    /// each file in it.
+    ///
+    /// Entries are in the order the archive lists them, named by their full
+    /// path within it, such as `docs/readme.txt`. Directories are skipped.
+    /// Invalid archives resolve to `FileReadError::Zip`.
+    ///
+    /// The whole archive is decompressed in memory, so this is only suited to
+    /// modestly sized uploads.
+    #[cfg(feature = "zip")]
+    pub fn unzip(&self) -> impl Future<Output = Result<Vec<(String, Vec<u8>)>, FileReadError>> {
+        let bytes = self.bytes();
+
+        async move { unzip(&bytes.await?).map_err(FileReadError::Zip) }
+    }
+
+    /// Returns the file's contents as the browser's own `ReadableStream`, as
+    /// given by [`Blob.stream()`](https://developer.mozilla.org/en-US/docs/Web/API/Blob/stream).
+    ///
+    /// Nothing is read until the stream is, and the bytes never pass through
+    /// wasm memory, so this is the cheapest way to hand a file to another web
+    /// API, such as the body of a streaming `fetch`.
+    #[cfg(feature = "streams")]
+    pub fn readable_stream(&self) -> web_sys::ReadableStream {
+        self.inner.stream()
+    }
+
+    /// Splits the file into consecutive chunks of `chunk_size` bytes, for
+    /// uploading in parts.
+    ///
+    /// Every chunk is exactly `chunk_size` bytes long, except for the last one,
+    /// which may be shorter, and an empty file has no chunks. This only
+    /// slices the file: nothing is read until a chunk's blob is.
+    ///
+    /// ```no_run
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// use gloo_file::Blob;
+    ///
+    /// for chunk in file.chunks(5 * 1024 * 1024) {
+    ///     let part = chunk.blob.array_buffer().await?;
+    ///     // Upload `part` as part number `chunk.index + 1`...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn chunks(&self, chunk_size: usize) -> impl Iterator<Item = BlobChunk> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        let blob = DataBlob::from_raw(self.inner.clone().into());
+        let size = blob.size();
+        let chunk_size = chunk_size as u64;
+        let count = size.div_ceil(chunk_size);
+
+        (0..count).map(move |index| {
+            let start = index * chunk_size;
+            let end = size.min(start + chunk_size);
+            BlobChunk {
+                index: index as usize,
+                start,
+                end,
+                blob: blob.slice(start, end),
+            }
+        })
+    }
+
+    /// Reads the file as a stream of chunks of `chunk_size` bytes, so that it
+    /// never needs to be held in memory all at once.
+    ///
+    /// Every chunk is exactly `chunk_size` bytes long, except for the last one,
+    /// which may be shorter. Chunks are read one at a time, as the stream is
+    /// polled, and the stream ends after the first error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn stream_chunks(
+        &self,
+        chunk_size: usize,
+    ) -> impl Stream<Item = Result<Vec<u8>, FileReadError>> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        let blob = DataBlob::from_raw(self.inner.clone().into());
+        let size = blob.size();
+        let reader = FileReader::new().map_err(Error::into_read_error);
+
+        stream::unfold(Some((blob, 0, reader)), move |state| async move {
+            let (blob, start, reader) = state?;
+            if start >= size {
+                return None;
+            }
+            let reader = match reader {
+                Ok(reader) => reader,
+                Err(error) => return Some((Err(error), None)),
+            };
+
+            let end = size.min(start + chunk_size as u64);
+            match reader.read_as_array_buffer(&blob.slice(start, end)).await {
+                Ok(chunk) => Some((Ok(chunk), Some((blob, end, Ok(reader))))),
+                Err(error) => Some((Err(error), None)),
+            }
+        })
+    }
+
+    /// Reads the file as a stream of lines of text, 64 KB at a time, so that
+    /// large files never need to be held in memory all at once.
+    ///
+    /// Lines are split on `\n`, with a trailing `\r` removed, and the last
+    /// line is yielded even without a newline after it. The text is UTF-8,
+    /// with invalid sequences replaced by `U+FFFD`. Characters split between
+    /// chunks are decoded whole, as each line is only decoded once its end
+    /// has been read. The stream ends after the first error.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example(file: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let mut lines = Box::pin(file.lines());
+    /// while let Some(line) = lines.next().await {
+    ///     let fields: Vec<&str> = line?.split(',').collect();
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lines(&self) -> impl Stream<Item = Result<String, FileReadError>> {
+        const CHUNK_SIZE: usize = 64 * 1024;
+        let chunks = Box::pin(self.stream_chunks(CHUNK_SIZE));
+
+        stream::unfold(
+            (chunks, LineSplitter::default(), false),
+            |(mut chunks, mut lines, mut ended)| async move {
+                loop {
+                    if let Some(line) = lines.lines.pop_front() {
+                        return Some((Ok(line), (chunks, lines, ended)));
+                    }
+                    if ended {
+                        return None;
+                    }
+                    match chunks.next().await {
+                        Some(Ok(chunk)) => lines.push(&chunk),
+                        Some(Err(error)) => return Some((Err(error), (chunks, lines, true))),
+                        None => {
+                            lines.finish();
+                            ended = true;
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// One of the chunks of a file, from [`File::chunks`](struct.File.html#method.chunks).
+#[derive(Debug)]
+pub struct BlobChunk {
+    /// The position of the chunk among the file's chunks, from `0`.
+    pub index: usize,
+    /// The offset in the file of the chunk's first byte.
+    pub start: u64,
+    /// The offset in the file just past the chunk's last byte.
+    pub end: u64,
+    /// The chunk's bytes, sliced from the file but not yet read.
+    pub blob: DataBlob,
+}
+
+/// Splits chunks of bytes into lines, holding on to a line until its end
+/// is pushed.
+///
+/// A `\n` byte is never part of a multi-byte UTF-8 character, so splitting
+/// on it before decoding can't break a character apart.
+#[derive(Default)]
+struct LineSplitter {
+    partial: Vec<u8>,
+    lines: VecDeque<String>,
+}
+
+impl LineSplitter {
+    fn push(&mut self, mut chunk: &[u8]) {
+        while let Some(end) = chunk.iter().position(|&byte| byte == b'\n') {
+            self.partial.extend_from_slice(&chunk[..end]);
+            let line = mem::take(&mut self.partial);
+            self.lines.push_back(decode_line(line));
+            chunk = &chunk[end + 1..];
+        }
+        self.partial.extend_from_slice(chunk);
+    }
+
+    /// Ends the last line, if it didn't end with a newline.
+    fn finish(&mut self) {
+        if !self.partial.is_empty() {
+            let line = mem::take(&mut self.partial);
+            self.lines.push_back(decode_line(line));
+        }
+    }
+}
+
+/// The heuristic behind [`File::is_probably_text`].
+fn looks_like_text(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return false;
+    }
+
+    let mut suspicious = 0;
+    let mut chunks = bytes.utf8_chunks().peekable();
+    while let Some(chunk) = chunks.next() {
+        suspicious += chunk
+            .valid()
+            .chars()
+            .filter(|&c| c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0c' | '\x1b'))
+            .count();
+        // A character cut off at the end of the bytes read isn't invalid.
+        let cut_off = chunks.peek().is_none() && chunk.invalid().len() < 4;
+        if !cut_off {
+            suspicious += chunk.invalid().len();
+        }
+    }
+    suspicious * 10 <= bytes.len()
+}
+
+/// The global `performance`, which windows and workers both have.
+fn performance() -> Result<web_sys::Performance> {
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance"))?
+        .dyn_into()
+        .map_err(|_| Error::Unsupported("performance"))
+}
+
+/// Decodes `bytes` in the encoding their byte-order mark gives, without the
+/// mark, or as UTF-8 if they have none.
+pub(crate) fn decode_with_bom(bytes: &[u8]) -> String {
+    match bytes {
+        [0xef, 0xbb, 0xbf, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        [0xff, 0xfe, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xfe, 0xff, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+pub(crate) fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    // A dangling odd byte is half a code unit, so it decodes as invalid.
+    let units = bytes.chunks(2).map(|pair| match *pair {
+        [a, b] => unit([a, b]),
+        _ => 0xd800,
+    });
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+fn decode_line(mut line: Vec<u8>) -> String {
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line)
+        .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned())
+}
+
+impl From<web_sys::File> for File {
+    fn from(inner: web_sys::File) -> File {
+        File::from_raw(inner)
+    }
+}
+
+impl fmt::Debug for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("File")
+            .field("name", &self.name)
+            .field("size", &self.size())
+            .field("mime_type", &self.mime_type())
+            .finish()
+    }
+}
+
+/// Shows the file's name, size and type, e.g. `report.pdf (12.3 KB, application/pdf)`.
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}", self.name, ByteSize(self.size_bytes()))?;
+        match self.mime_type() {
+            MimeType::Unknown => f.write_str(")"),
+            mime => write!(f, ", {})", mime),
+        }
+    }
+}
+
+/// Files are compared by their metadata, ordering by name, then size, then
+/// last modification time.
+///
+/// This isn't object identity: two different files with the same metadata
+/// are equal.
+impl PartialEq for File {
+    fn eq(&self, other: &File) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for File {}
+
+impl PartialOrd for File {
+    fn partial_cmp(&self, other: &File) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for File {
+    fn cmp(&self, other: &File) -> Ordering {
+        self.name
+            .cmp(&other.name)
+            .then_with(|| self.size().cmp(&other.size()))
+            .then_with(|| self.last_modified().total_cmp(&other.last_modified()))
+    }
+}
+
+impl Blob for File {
+    fn size(&self) -> u64 {
+        self.inner.size() as u64
+    }
+
+    fn raw_type(&self) -> String {
+        self.inner.type_()
+    }
+}
+
+impl RawBlob for File {
+    fn raw(&self) -> &web_sys::Blob {
+        &self.inner
+    }
+}
+
+/// Compares two digests without stopping at the first byte which differs.
+#[cfg(feature = "digest")]
+fn constant_time_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    let difference = a
+        .iter()
+        .zip(b)
+        .fold(0, |difference, (a, b)| difference | (a ^ b));
+    std::hint::black_box(difference) == 0
+}
+
+#[cfg(feature = "digest")]
+async fn sha256(data: &js_sys::Object) -> Result<[u8; 32], JsValue> {
+    // `crypto` is a global in both windows and workers.
+    let crypto: web_sys::Crypto =
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("crypto"))?.dyn_into()?;
+    let promise = crypto
+        .subtle()
+        .digest_with_str_and_buffer_source("SHA-256", data)?;
+    let digest = wasm_bindgen_futures::JsFuture::from(promise).await?;
+
+    let mut bytes = [0; 32];
+    js_sys::Uint8Array::new(&digest).copy_to(&mut bytes);
+    Ok(bytes)
+}
+
+#[cfg(feature = "zip")]
+fn unzip(bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, zip::result::ZipError> {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let mut contents = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut contents)?;
+        let name = entry.name()?.into_owned();
+        entries.push((name, contents));
+    }
+    Ok(entries)
+}
+
+/// Encodes `bytes` as base64 with the standard alphabet and `=` padding.
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | u32::from(byte) << (16 - 8 * i)
+        });
+        // Three bytes make four characters, and one or two make two or three.
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3f;
+                encoded.push(ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,58 @@
+use crate::{Blob, File, FileReadError};
+
+/// Reads a [`File`](struct.File.html) piece by piece from a position which
+/// moves on with each read, like a file handle.
+///
+/// Obtained through [`File::cursor`](struct.File.html#method.cursor). Each
+/// read only loads the bytes asked for. Dropping a read before it finishes
+/// aborts it and leaves the position where it was.
+///
+/// ```no_run
+/// # async fn example(video: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+/// let mut cursor = video.cursor();
+/// let header = cursor.read(1024).await?;
+/// cursor.seek(1024 * 1024);
+/// let more = cursor.read(64 * 1024).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileCursor {
+    file: File,
+    position: u64,
+}
+
+impl FileCursor {
+    pub(crate) fn new(file: File) -> FileCursor {
+        FileCursor { file, position: 0 }
+    }
+
+    /// Reads up to `len` bytes from the current position, and moves the
+    /// position past them.
+    ///
+    /// The read is shorter than `len` when it reaches the end of the file,
+    /// and empty from the end on.
+    pub async fn read(&mut self, len: usize) -> Result<Vec<u8>, FileReadError> {
+        let end = self.position.saturating_add(len as u64);
+        let bytes = self.file.read_range(self.position, end).await?;
+        self.position += bytes.len() as u64;
+        Ok(bytes)
+    }
+
+    /// Moves the position to `position` bytes from the start of the file.
+    ///
+    /// Seeking past the end is allowed, and makes the next read empty.
+    pub fn seek(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    /// The offset in the file the next read starts at.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns `true` if the position is at or past the end of the file.
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.file.size()
+    }
+}
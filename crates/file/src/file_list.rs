@@ -0,0 +1,805 @@
+use crate::{
+    read_once, Blob, File, FileConstraints, FileReadError, FileReader, FileValidationError,
+    MimeCategory, MimeType, Result,
+};
+use futures::channel::mpsc;
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+/// The list of files selected in an `<input type="file">`.
+///
+/// Cloning a `FileList` is cheap: the clone refers to the same underlying list.
+#[derive(Clone)]
+pub struct FileList {
+    inner: web_sys::FileList,
+    length: usize,
+}
+
+impl FileList {
+    /// Returns the files selected in `input`.
+    ///
+    /// A file input with nothing selected gives an empty list. `None` is only
+    /// returned if `input` has no file list at all, because it isn't an
+    /// `<input type="file">`.
+    pub fn new(input: &web_sys::HtmlInputElement) -> Option<FileList> {
+        input.files().map(FileList::from_raw)
+    }
+
+    /// Returns the files selected in the `<input type="file">` that fired a
+    /// `change` or `input` event.
+    ///
+    /// This is `None` if the event's target isn't a file input.
+    ///
+    /// ```no_run
+    /// use gloo_file::FileList;
+    ///
+    /// fn on_change(event: &web_sys::Event) {
+    ///     if let Some(files) = FileList::from_change_event(event) {
+    ///         for file in &files {
+    ///             // ...
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn from_change_event(event: &web_sys::Event) -> Option<FileList> {
+        let input = event
+            .target()?
+            .dyn_into::<web_sys::HtmlInputElement>()
+            .ok()?;
+        if input.type_() != "file" {
+            return None;
+        }
+        FileList::new(&input)
+    }
+
+    /// Returns the files dropped in a `drop` event.
+    ///
+    /// This is `None` for drags which don't carry files, such as dragged text.
+    /// Dropped directories may give an empty list.
+    pub fn from_drag_event(event: &web_sys::DragEvent) -> Option<FileList> {
+        event.data_transfer()?.files().map(FileList::from_raw)
+    }
+
+    /// Returns the files pasted in a `paste` event.
+    ///
+    /// This is `None` for pastes which don't carry files, such as pasted
+    /// text. Pasted images are included even in browsers which only expose
+    /// them as clipboard items rather than as files.
+    pub fn from_clipboard_event(event: &web_sys::ClipboardEvent) -> Option<FileList> {
+        let data = event.clipboard_data()?;
+        let files = data
+            .files()
+            .map(FileList::from_raw)
+            .filter(|files| !files.is_empty());
+        if files.is_some() {
+            return files;
+        }
+
+        // Gather the file items into a list of their own.
+        let items = data.items();
+        let transfer = web_sys::DataTransfer::new().ok()?;
+        for index in 0..items.length() {
+            let file = items
+                .get(index)
+                .filter(|item| item.kind() == "file")
+                .and_then(|item| item.get_as_file().ok().flatten());
+            // An item the list won't take is skipped rather than losing the
+            // rest of the paste.
+            if let Some(file) = file {
+                let _ = transfer.items().add_with_file(&file);
+            }
+        }
+        transfer
+            .files()
+            .map(FileList::from_raw)
+            .filter(|files| !files.is_empty())
+    }
+
+    /// Returns a stream of the files selected in `input`, yielding a new list
+    /// each time the selection changes.
+    ///
+    /// The stream listens for the input's `change` event, and removes its
+    /// listener when dropped. It never ends on its own.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use gloo_file::FileList;
+    ///
+    /// # async fn example(input: web_sys::HtmlInputElement) {
+    /// let mut selections = FileList::watch(&input);
+    /// while let Some(files) = selections.next().await {
+    ///     // ...
+    /// }
+    /// # }
+    /// ```
+    pub fn watch(input: &web_sys::HtmlInputElement) -> impl Stream<Item = FileList> {
+        let (sender, receiver) = mpsc::unbounded();
+        let onchange = {
+            let input = input.clone();
+            Closure::wrap(Box::new(move || {
+                if let Some(files) = FileList::new(&input) {
+                    let _ = sender.unbounded_send(files);
+                }
+            }) as Box<dyn FnMut()>)
+        };
+        input
+            .add_event_listener_with_callback("change", onchange.as_ref().unchecked_ref())
+            .unwrap_throw();
+
+        Watch {
+            input: input.clone(),
+            onchange,
+            receiver,
+        }
+    }
+
+    /// Builds a list of files which are already at hand, for example to merge
+    /// the files from a drop with those from a picker.
+    ///
+    /// The browser doesn't let a `web_sys::FileList` be constructed
+    /// directly, so the files are gathered through a `DataTransfer`, just as
+    /// a drag carries them. The result is a real list, which can be passed to
+    /// other web APIs through [`as_raw`](#method.as_raw) and behaves exactly
+    /// like a list from an input. This fails where `DataTransfer` is
+    /// unavailable, such as in workers.
+    ///
+    /// ```no_run
+    /// use gloo_file::FileList;
+    ///
+    /// # fn example(dropped: FileList, picked: FileList) -> gloo_file::Result<()> {
+    /// let all = FileList::from_files(dropped.iter().chain(&picked))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_files<I>(files: I) -> Result<FileList>
+    where
+        I: IntoIterator<Item = File>,
+    {
+        let transfer = web_sys::DataTransfer::new()?;
+        let items = transfer.items();
+        for file in files {
+            items.add_with_file(file.as_raw())?;
+        }
+        // A new transfer always has a file list.
+        Ok(FileList::from_raw(transfer.files().unwrap_throw()))
+    }
+
+    /// Wraps a `web_sys::FileList` obtained from another web API.
+    ///
+    /// This is the same as `FileList::from(inner)`.
+    pub fn from_raw(inner: web_sys::FileList) -> FileList {
+        FileList {
+            length: inner.length() as usize,
+            inner,
+        }
+    }
+
+    /// Returns the file at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<File> {
+        self.inner.get(index as u32).map(File::from_raw)
+    }
+
+    /// The number of files in the list.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the list contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// The underlying `web_sys::FileList`.
+    pub fn as_raw(&self) -> &web_sys::FileList {
+        &self.inner
+    }
+
+    /// Unwraps the underlying `web_sys::FileList`.
+    pub fn into_raw(self) -> web_sys::FileList {
+        self.inner
+    }
+
+    /// Iterates over the files in the list.
+    pub fn iter(&self) -> FileListIter<'_> {
+        FileListIter {
+            file_list: self,
+            current: 0,
+        }
+    }
+
+    /// The names of the files in the list, in list order.
+    pub fn names(&self) -> Vec<String> {
+        self.iter().map(|file| file.name().to_string()).collect()
+    }
+
+    /// Iterates over the files in the list along with their names, in list
+    /// order.
+    pub fn named_iter(&self) -> impl Iterator<Item = (String, File)> + '_ {
+        self.iter().map(|file| (file.name().to_string(), file))
+    }
+
+    /// Iterates over the files in the list along with their indices, which
+    /// make stable keys when rendering the selection.
+    ///
+    /// Unlike `iter().enumerate()`, the iterator holds its own handle to the
+    /// list rather than borrowing this one, so it can be returned or stored.
+    pub fn enumerate(&self) -> impl Iterator<Item = (usize, File)> {
+        self.clone().into_iter().enumerate()
+    }
+
+    /// Reads every file in the list as a UTF-8 string, all at once.
+    ///
+    /// The results are in list order, with one per file.
+    pub fn read_all_text(&self) -> impl Future<Output = Vec<Result<String, FileReadError>>> {
+        future::join_all(self.iter().map(|file| file.text()))
+    }
+
+    /// Reads every file in the list as raw bytes, all at once.
+    ///
+    /// The results are in list order, with one per file.
+    pub fn read_all_bytes(&self) -> impl Future<Output = Vec<Result<Vec<u8>, FileReadError>>> {
+        future::join_all(self.iter().map(|file| file.bytes()))
+    }
+
+    /// Reads every file in the list as raw bytes, all at once, sorting the
+    /// files into those which were read and those which failed.
+    ///
+    /// Each file is read on its own, so one failing doesn't affect the
+    /// others. Both halves of the report are in list order.
+    ///
+    /// ```no_run
+    /// # async fn example(files: gloo_file::FileList) {
+    /// let report = files.read_all_report().await;
+    /// let message = format!(
+    ///     "{} of {} files read, {} failed",
+    ///     report.succeeded.len(),
+    ///     files.len(),
+    ///     report.failed.len(),
+    /// );
+    /// # }
+    /// ```
+    pub fn read_all_report(&self) -> impl Future<Output = ReadReport> {
+        let reads = future::join_all(self.iter().map(|file| {
+            let bytes = file.bytes();
+            async move { (file, bytes.await) }
+        }));
+
+        async move {
+            let mut report = ReadReport {
+                succeeded: Vec::new(),
+                failed: Vec::new(),
+            };
+            for (file, result) in reads.await {
+                match result {
+                    Ok(bytes) => report.succeeded.push((file, bytes)),
+                    Err(error) => report.failed.push((file, error)),
+                }
+            }
+            report
+        }
+    }
+
+    /// Reads every file in the list as raw bytes, all at once, reporting the
+    /// progress of the whole batch.
+    ///
+    /// `on_progress` is called with the index of the file which progressed,
+    /// the number of files, and the fraction of the batch read so far,
+    /// between `0.0` and `1.0`. Files count towards the fraction by their
+    /// size, and a file which fails counts as done, so the fraction always
+    /// reaches exactly `1.0` once every read has finished.
+    ///
+    /// ```no_run
+    /// # async fn example(files: gloo_file::FileList) {
+    /// let contents = files
+    ///     .read_all_with_progress(|index, total, fraction| {
+    ///         // Update a progress bar...
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    pub fn read_all_with_progress<F>(
+        &self,
+        on_progress: F,
+    ) -> impl Future<Output = Vec<Result<Vec<u8>, FileReadError>>>
+    where
+        F: FnMut(usize, usize, f64) + 'static,
+    {
+        let progress = Rc::new(BatchProgress {
+            callback: RefCell::new(Box::new(on_progress)),
+            fractions: RefCell::new(vec![0.0; self.length]),
+            sizes: self.iter().map(|file| file.size()).collect(),
+        });
+
+        let reads: Vec<_> = self
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let reader = FileReader::new().map(|mut reader| {
+                    let progress = progress.clone();
+                    reader.on_progress(move |fraction| progress.report(index, fraction));
+                    reader
+                });
+                let read = read_once(reader.map(|reader| reader.read_as_array_buffer(&file)));
+                let progress = progress.clone();
+
+                async move {
+                    let result = read.await;
+                    progress.finish(index);
+                    result
+                }
+            })
+            .collect();
+        future::join_all(reads)
+    }
+
+    /// Reads every file in the list as a UTF-8 string, at most `limit` at a
+    /// time.
+    ///
+    /// The results are in list order, with one per file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`.
+    pub fn read_all_text_buffered(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Vec<Result<String, FileReadError>>> {
+        assert!(limit != 0, "`limit` must be at least 1");
+        stream::iter(self.clone())
+            .map(|file| file.text())
+            .buffered(limit)
+            .collect()
+    }
+
+    /// Reads every file in the list as raw bytes, at most `limit` at a time.
+    ///
+    /// The results are in list order, with one per file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is `0`.
+    pub fn read_all_bytes_buffered(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Vec<Result<Vec<u8>, FileReadError>>> {
+        assert!(limit != 0, "`limit` must be at least 1");
+        stream::iter(self.clone())
+            .map(|file| file.bytes())
+            .buffered(limit)
+            .collect()
+    }
+
+    /// Reads the files in the list as raw bytes, at most `concurrency` at a
+    /// time, yielding each as soon as it has been read.
+    ///
+    /// Unlike [`read_all_bytes_buffered`](#method.read_all_bytes_buffered),
+    /// results come in the order the reads finish, so one slow file doesn't
+    /// hold back the others, and each is paired with its file to tell them
+    /// apart. A new read starts whenever one finishes, as the stream is
+    /// polled. A file which fails to read yields an error, and the other
+    /// reads carry on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `concurrency` is `0`.
+    pub fn read_all_bounded(
+        &self,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<(File, Vec<u8>), FileReadError>> {
+        assert!(concurrency != 0, "`concurrency` must be at least 1");
+        stream::iter(self.clone())
+            .map(|file| {
+                let bytes = file.bytes();
+                async move { Ok((file, bytes.await?)) }
+            })
+            .buffer_unordered(concurrency)
+    }
+
+    /// Reads the files in the list one at a time, as the stream is polled.
+    ///
+    /// The next file isn't read until the previous one has been taken from
+    /// the stream, so dropping the stream stops reading. A file which fails
+    /// to read yields an error, and the stream moves on to the next file.
+    pub fn into_read_stream(self) -> impl Stream<Item = Result<(File, Vec<u8>), FileReadError>> {
+        stream::iter(self).then(|file| {
+            let bytes = file.bytes();
+            async move { Ok((file, bytes.await?)) }
+        })
+    }
+
+    /// Reads the files in the list as UTF-8 text one at a time, as the
+    /// stream is polled, like [`into_read_stream`](#method.into_read_stream).
+    ///
+    /// Files are yielded in list order. The stream holds its own reference to
+    /// the list, so it doesn't borrow `self`.
+    pub fn iter_text(&self) -> impl Stream<Item = Result<(File, String), FileReadError>> {
+        stream::iter(self.clone()).then(|file| {
+            let text = file.text();
+            async move { Ok((file, text.await?)) }
+        })
+    }
+
+    /// Reads files from the start of the list, one at a time, for as long as
+    /// their combined size stays within `max_total_bytes`.
+    ///
+    /// Reading stops at the first file which would take the total over the
+    /// budget, even if a smaller file after it would fit, so the files read
+    /// are always a prefix of the list. Files are checked by their reported
+    /// size before they are read, so nothing beyond the budget is loaded. A
+    /// file which fails to read is left out of the result.
+    ///
+    /// The returned files are in list order; any file not among them still
+    /// needs handling.
+    pub fn read_within_budget(
+        &self,
+        max_total_bytes: u64,
+    ) -> impl Future<Output = Vec<(File, Vec<u8>)>> {
+        let mut total = 0u64;
+        let files: Vec<File> = self
+            .iter()
+            .take_while(|file| match total.checked_add(file.size()) {
+                Some(next) if next <= max_total_bytes => {
+                    total = next;
+                    true
+                }
+                _ => false,
+            })
+            .collect();
+
+        async move {
+            let mut read = Vec::with_capacity(files.len());
+            for file in files {
+                if let Ok(bytes) = file.bytes().await {
+                    read.push((file, bytes));
+                }
+            }
+            read
+        }
+    }
+
+    /// Returns the files in the list whose type is `mime`.
+    ///
+    /// Types are compared by their canonical MIME string, so an `Other` type
+    /// only matches files which reported exactly the same type string.
+    pub fn filter_by_type(&self, mime: MimeType) -> Vec<File> {
+        self.iter()
+            .filter(|file| file.mime_type().as_str() == mime.as_str())
+            .collect()
+    }
+
+    /// Groups the files in the list by the
+    /// [`category`](enum.MimeType.html#method.category) of their type.
+    ///
+    /// Only categories with at least one file are present, and each group
+    /// is in list order.
+    ///
+    /// ```no_run
+    /// use gloo_file::MimeCategory;
+    ///
+    /// # fn example(files: gloo_file::FileList) {
+    /// let mut groups = files.partition_by_category();
+    /// let images = groups.remove(&MimeCategory::Image).unwrap_or_default();
+    /// # }
+    /// ```
+    pub fn partition_by_category(&self) -> HashMap<MimeCategory, Vec<File>> {
+        let mut groups: HashMap<MimeCategory, Vec<File>> = HashMap::new();
+        for file in self {
+            groups
+                .entry(file.mime_type().category())
+                .or_default()
+                .push(file);
+        }
+        groups
+    }
+
+    /// The combined size of every file in the list, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.iter().map(|file| file.size()).sum()
+    }
+
+    /// Returns the largest file in the list, or the first of them if several
+    /// are the same size.
+    ///
+    /// This is `None` only for an empty list.
+    pub fn largest(&self) -> Option<File> {
+        self.iter().reduce(|largest, file| {
+            if file.size() > largest.size() {
+                file
+            } else {
+                largest
+            }
+        })
+    }
+
+    /// Returns the files in the list, without those which repeat an earlier
+    /// file.
+    ///
+    /// Files are the same when their names, sizes and modification times
+    /// are, since the browser gives no other way to tell whether two `File`s
+    /// are the same file on disk. The first of each is kept, in list order.
+    pub fn dedup(&self) -> Vec<File> {
+        let mut seen = HashSet::new();
+        self.iter()
+            .filter(|file| {
+                seen.insert((
+                    file.name().to_string(),
+                    file.size(),
+                    file.last_modified().to_bits(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the files in the list, without those whose contents repeat an
+    /// earlier file's, whatever their names.
+    ///
+    /// Contents are compared by their
+    /// [`digest_sha256`](struct.File.html#method.digest_sha256). Only files
+    /// which share their size with another file are hashed, one at a time,
+    /// so a list of differently sized files is never read at all. A file
+    /// which fails to hash is kept, since it can't be shown to be a
+    /// duplicate. The first of each is kept, in list order.
+    #[cfg(feature = "digest")]
+    pub fn dedup_by_content(&self) -> impl Future<Output = Vec<File>> {
+        let files: Vec<File> = self.iter().collect();
+        let mut sizes: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (index, file) in files.iter().enumerate() {
+            sizes.entry(file.size()).or_default().push(index);
+        }
+
+        async move {
+            let mut keep = vec![true; files.len()];
+            for bucket in sizes.values().filter(|bucket| bucket.len() > 1) {
+                let mut digests = HashSet::new();
+                for &index in bucket {
+                    if let Ok(digest) = files[index].digest_sha256().await {
+                        keep[index] = digests.insert(digest);
+                    }
+                }
+            }
+
+            files
+                .into_iter()
+                .zip(keep)
+                .filter_map(|(file, keep)| if keep { Some(file) } else { None })
+                .collect()
+        }
+    }
+
+    /// Returns the directory every file in the list is in, for files picked
+    /// through an `<input type="file" webkitdirectory>`.
+    ///
+    /// This is the longest path shared by the files'
+    /// [`relative_path`](struct.File.html#method.relative_path)s, without a
+    /// trailing `/`, such as `"photos"` for `photos/a.jpg` and
+    /// `photos/2020/b.jpg`. A single file gives its parent directory. Files
+    /// without a relative path are in no directory, so this is `None` if any
+    /// of them are there, or if the list is empty.
+    pub fn common_directory(&self) -> Option<String> {
+        let mut common: Option<Vec<String>> = None;
+        for file in self {
+            let path = file.relative_path().unwrap_or_default();
+            let mut directories: Vec<&str> = path.split('/').collect();
+            // The last component is the file's own name.
+            directories.pop();
+
+            let common = common.get_or_insert_with(|| {
+                directories
+                    .iter()
+                    .map(|directory| directory.to_string())
+                    .collect()
+            });
+            let shared = common
+                .iter()
+                .zip(&directories)
+                .take_while(|(a, b)| a == *b)
+                .count();
+            common.truncate(shared);
+        }
+
+        common
+            .filter(|common| !common.is_empty())
+            .map(|common| common.join("/"))
+    }
+
+    /// Checks every file in the list against `constraints`, before any of
+    /// them are read.
+    ///
+    /// All of the violations are returned rather than just the first, so
+    /// they can be shown to the user together. A selection with too many
+    /// files comes first, followed by each file's problems in list order.
+    ///
+    /// ```no_run
+    /// use gloo_file::{FileConstraints, FileList};
+    ///
+    /// fn problems(files: &FileList) -> Vec<String> {
+    ///     let constraints = FileConstraints {
+    ///         max_size: Some(10 * 1024 * 1024),
+    ///         accept: Some("image/*".to_string()),
+    ///         max_files: Some(5),
+    ///     };
+    ///     match files.validate(&constraints) {
+    ///         Ok(()) => Vec::new(),
+    ///         Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    ///     }
+    /// }
+    /// ```
+    pub fn validate(&self, constraints: &FileConstraints) -> Result<(), Vec<FileValidationError>> {
+        let mut errors = Vec::new();
+        if let Some(max) = constraints.max_files {
+            if self.len() > max {
+                errors.push(FileValidationError::TooManyFiles {
+                    count: self.len(),
+                    max,
+                });
+            }
+        }
+        for file in self {
+            constraints.check(&file, &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl From<web_sys::FileList> for FileList {
+    fn from(inner: web_sys::FileList) -> FileList {
+        FileList::from_raw(inner)
+    }
+}
+
+impl<'a> IntoIterator for &'a FileList {
+    type Item = File;
+    type IntoIter = FileListIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for FileList {
+    type Item = File;
+    type IntoIter = FileListIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        FileListIntoIter {
+            file_list: self,
+            current: 0,
+        }
+    }
+}
+
+impl fmt::Debug for FileList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileList")
+            .field("len", &self.length)
+            .finish()
+    }
+}
+
+/// The outcome of reading every file in a [`FileList`](struct.FileList.html),
+/// from [`FileList::read_all_report`](struct.FileList.html#method.read_all_report).
+#[derive(Debug)]
+pub struct ReadReport {
+    /// The files which were read, with their contents.
+    pub succeeded: Vec<(File, Vec<u8>)>,
+    /// The files which couldn't be read, with the reason.
+    pub failed: Vec<(File, FileReadError)>,
+}
+
+/// An iterator over the files in a [`FileList`](struct.FileList.html).
+#[derive(Debug)]
+pub struct FileListIter<'a> {
+    file_list: &'a FileList,
+    current: usize,
+}
+
+impl<'a> Iterator for FileListIter<'a> {
+    type Item = File;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current >= self.file_list.len() {
+            return None;
+        }
+        // If the browser's list no longer has the file, end the iteration
+        // early rather than panicking.
+        let file = self.file_list.get(self.current)?;
+        self.current += 1;
+        Some(file)
+    }
+}
+
+/// An iterator which owns a [`FileList`](struct.FileList.html) and yields its
+/// files, so that it can be returned without borrowing the list.
+#[derive(Debug)]
+pub struct FileListIntoIter {
+    file_list: FileList,
+    current: usize,
+}
+
+impl Iterator for FileListIntoIter {
+    type Item = File;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let file = self.file_list.get(self.current)?;
+        self.current += 1;
+        Some(file)
+    }
+}
+
+/// The stream returned by `FileList::watch`, which stops listening when
+/// dropped.
+struct Watch {
+    input: web_sys::HtmlInputElement,
+    onchange: Closure<dyn FnMut()>,
+    receiver: mpsc::UnboundedReceiver<FileList>,
+}
+
+impl Stream for Watch {
+    type Item = FileList;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<FileList>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        let _ = self
+            .input
+            .remove_event_listener_with_callback("change", self.onchange.as_ref().unchecked_ref());
+    }
+}
+
+/// The progress of the reads started by `FileList::read_all_with_progress`.
+struct BatchProgress {
+    callback: RefCell<BatchCallback>,
+    fractions: RefCell<Vec<f64>>,
+    sizes: Vec<u64>,
+}
+
+type BatchCallback = Box<dyn FnMut(usize, usize, f64)>;
+
+impl BatchProgress {
+    fn report(&self, index: usize, fraction: f64) {
+        let overall = {
+            let mut fractions = self.fractions.borrow_mut();
+            fractions[index] = fraction;
+
+            let total: u64 = self.sizes.iter().sum();
+            if total == 0 {
+                // Only empty files, so weigh them all the same.
+                fractions.iter().sum::<f64>() / fractions.len() as f64
+            } else {
+                let loaded: f64 = fractions
+                    .iter()
+                    .zip(&self.sizes)
+                    .map(|(fraction, &size)| fraction * size as f64)
+                    .sum();
+                loaded / total as f64
+            }
+        };
+        (self.callback.borrow_mut())(index, self.sizes.len(), overall);
+    }
+
+    /// Reports the read of the file at `index` as done, unless its last
+    /// progress event already did.
+    fn finish(&self, index: usize) {
+        if self.fractions.borrow()[index] < 1.0 {
+            self.report(index, 1.0);
+        }
+    }
+}
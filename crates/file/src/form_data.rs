@@ -0,0 +1,28 @@
+use crate::{File, RawBlob, Result};
+
+/// Appends `file` to `form` as the field `field`, keeping its name as the
+/// uploaded filename.
+///
+/// ```no_run
+/// use gloo_file::{append_to_form_data, File};
+///
+/// # fn example(file: &File) -> gloo_file::Result<()> {
+/// let form = web_sys::FormData::new()?;
+/// append_to_form_data(&form, "upload", file)?;
+/// // Send `form` as the body of a `fetch`...
+/// # Ok(())
+/// # }
+/// ```
+pub fn append_to_form_data(form: &web_sys::FormData, field: &str, file: &File) -> Result<()> {
+    append_blob_to_form_data(form, field, file, file.name())
+}
+
+/// Appends `blob` to `form` as the field `field`, uploaded as `filename`.
+pub fn append_blob_to_form_data(
+    form: &web_sys::FormData,
+    field: &str,
+    blob: &impl RawBlob,
+    filename: &str,
+) -> Result<()> {
+    Ok(form.append_with_blob_and_filename(field, blob.raw(), filename)?)
+}
@@ -0,0 +1,143 @@
+use crate::{Error, File, Result};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Options for [`open_file_picker`](fn.open_file_picker.html).
+#[derive(Debug, Clone, Default)]
+pub struct FilePickerOptions {
+    /// Lets the user pick more than one file.
+    pub multiple: bool,
+    /// The types of file the picker offers, as filters the user can choose
+    /// between. Any file can be picked if this is empty.
+    pub types: Vec<FilePickerType>,
+    /// Hides the picker's "All files" filter, so only `types` can be picked.
+    pub exclude_accept_all_option: bool,
+}
+
+/// A type of file offered by [`open_file_picker`](fn.open_file_picker.html).
+#[derive(Debug, Clone)]
+pub struct FilePickerType {
+    /// The name of the type shown to the user, e.g. `"Images"`.
+    pub description: Option<String>,
+    /// The MIME type of the files, e.g. `"image/*"`.
+    pub mime_type: String,
+    /// The extensions of the files, including the dot, e.g. `".png"`.
+    pub extensions: Vec<String>,
+}
+
+/// Lets the user pick files with the
+/// [File System Access API](https://developer.mozilla.org/en-US/docs/Web/API/Window/showOpenFilePicker).
+///
+/// This fails if the browser doesn't support the API, or with an
+/// `AbortError` if the user closes the picker without picking anything.
+///
+/// ```no_run
+/// use gloo_file::{open_file_picker, FilePickerOptions};
+///
+/// # async fn example() -> gloo_file::Result<()> {
+/// let options = FilePickerOptions {
+///     multiple: true,
+///     ..FilePickerOptions::default()
+/// };
+/// for file in open_file_picker(&options).await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn open_file_picker(options: &FilePickerOptions) -> Result<Vec<File>> {
+    let window = web_sys::window().ok_or(Error::Unsupported("the window"))?;
+    // `showOpenFilePicker` is only in `web-sys`'s unstable APIs.
+    let show = js_sys::Reflect::get(&window, &JsValue::from_str("showOpenFilePicker"))?
+        .dyn_into::<js_sys::Function>()
+        .map_err(|_| Error::Unsupported("the File System Access API"))?;
+
+    let promise: js_sys::Promise = show.call1(&window, &options.to_js()?)?.unchecked_into();
+    let handles: js_sys::Array = JsFuture::from(promise).await?.unchecked_into();
+
+    let mut files = Vec::with_capacity(handles.length() as usize);
+    for handle in handles.iter() {
+        let get_file: js_sys::Function =
+            js_sys::Reflect::get(&handle, &JsValue::from_str("getFile"))?.unchecked_into();
+        let promise: js_sys::Promise = get_file.call0(&handle)?.unchecked_into();
+        let file: web_sys::File = JsFuture::from(promise).await?.unchecked_into();
+        files.push(File::from_raw(file));
+    }
+    Ok(files)
+}
+
+/// Lists the files in the directory `dir`, such as one picked with
+/// `showDirectoryPicker` or the root of the
+/// [origin private file system](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system).
+///
+/// Subdirectories are skipped, rather than listed or descended into. Files
+/// are in the order the browser lists them, which is unspecified.
+///
+/// ```no_run
+/// use gloo_file::files_in_directory;
+///
+/// # async fn example() -> gloo_file::Result<()> {
+/// let storage = web_sys::window().unwrap().navigator().storage();
+/// let root = wasm_bindgen_futures::JsFuture::from(storage.get_directory()).await?;
+/// for file in files_in_directory(&root.into()).await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn files_in_directory(dir: &web_sys::FileSystemDirectoryHandle) -> Result<Vec<File>> {
+    let handles = dir.values();
+    let mut files = Vec::new();
+    loop {
+        let next: js_sys::IteratorNext = JsFuture::from(handles.next()?).await?.unchecked_into();
+        if next.done() {
+            break;
+        }
+        let handle: web_sys::FileSystemHandle = next.value().unchecked_into();
+        if handle.kind() != web_sys::FileSystemHandleKind::File {
+            continue;
+        }
+        let handle: web_sys::FileSystemFileHandle = handle.unchecked_into();
+        let file: web_sys::File = JsFuture::from(handle.get_file()).await?.unchecked_into();
+        files.push(File::from_raw(file));
+    }
+    Ok(files)
+}
+
+impl FilePickerOptions {
+    fn to_js(&self) -> Result<JsValue, JsValue> {
+        let types = js_sys::Array::new();
+        for kind in &self.types {
+            let extensions: js_sys::Array = kind
+                .extensions
+                .iter()
+                .map(|e| JsValue::from_str(e))
+                .collect();
+            let accept = js_sys::Object::new();
+            js_sys::Reflect::set(&accept, &JsValue::from_str(&kind.mime_type), &extensions)?;
+
+            let entry = js_sys::Object::new();
+            if let Some(description) = &kind.description {
+                set(&entry, "description", &JsValue::from_str(description))?;
+            }
+            set(&entry, "accept", &accept)?;
+            types.push(&entry);
+        }
+
+        let options = js_sys::Object::new();
+        set(&options, "multiple", &JsValue::from_bool(self.multiple))?;
+        set(
+            &options,
+            "excludeAcceptAllOption",
+            &JsValue::from_bool(self.exclude_accept_all_option),
+        )?;
+        if !self.types.is_empty() {
+            set(&options, "types", &types)?;
+        }
+        Ok(options.into())
+    }
+}
+
+fn set(object: &js_sys::Object, key: &str, value: &JsValue) -> Result<(), JsValue> {
+    js_sys::Reflect::set(object, &JsValue::from_str(key), value).map(drop)
+}
@@ -0,0 +1,190 @@
+use crate::{Error, File, FileReadError, ObjectUrl, Settle};
+use futures::channel::oneshot;
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+impl File {
+    /// Decodes the file as an image and returns its width and height in
+    /// pixels.
+    ///
+    /// The image is loaded through a temporary object URL into an
+    /// `HtmlImageElement`, so this needs a window. Files the browser can't
+    /// decode as an image resolve to `FileReadError::InvalidImage`.
+    pub fn image_dimensions(&self) -> impl Future<Output = Result<(u32, u32), FileReadError>> {
+        let url = ObjectUrl::new(self);
+        let image = web_sys::HtmlImageElement::new();
+
+        async move {
+            let image = image.map_err(FileReadError::JsError)?;
+            let (sender, receiver) = oneshot::channel();
+            let sender = Settle(Rc::new(RefCell::new(Some(sender))));
+
+            let onload = {
+                let sender = sender.clone();
+                let image = image.clone();
+                Closure::once(move || {
+                    sender.send(|| Ok((image.natural_width(), image.natural_height())));
+                })
+            };
+            let onerror = Closure::once(move || {
+                sender.send(|| Err(FileReadError::InvalidImage));
+            });
+            image.set_onload(Some(onload.as_ref().unchecked_ref()));
+            image.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+            image.set_src(&url);
+
+            let result = receiver.await;
+            // The handlers hold the image, so unset them to break the cycle.
+            image.set_onload(None);
+            image.set_onerror(None);
+            drop((onload, onerror, url));
+            result.unwrap_or(Err(FileReadError::Aborted))
+        }
+    }
+
+    /// Decodes the file into an `ImageBitmap` with
+    /// [`createImageBitmap()`](https://developer.mozilla.org/en-US/docs/Web/API/createImageBitmap),
+    /// ready to be drawn to a canvas or uploaded as a WebGL texture.
+    ///
+    /// Unlike [`image_dimensions`](#method.image_dimensions) this works in
+    /// workers as well as windows. Files the browser can't decode as an image
+    /// resolve to `FileReadError::InvalidImage`.
+    pub fn decode_bitmap(
+        &self,
+    ) -> impl Future<Output = Result<web_sys::ImageBitmap, FileReadError>> {
+        let promise = create_image_bitmap(self.as_raw());
+
+        async move {
+            let promise = promise.map_err(FileReadError::JsError)?;
+            match JsFuture::from(promise).await {
+                Ok(bitmap) => Ok(bitmap.unchecked_into()),
+                Err(error) => {
+                    let undecodable = error
+                        .dyn_ref::<web_sys::DomException>()
+                        .is_some_and(|exception| exception.name() == "InvalidStateError");
+                    if undecodable {
+                        Err(FileReadError::InvalidImage)
+                    } else {
+                        Err(FileReadError::from_js(error))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl File {
+    /// Decodes the file as an image and re-encodes it as a `data:` URL of
+    /// type `mime`, scaled down to fit within `max_dim` pixels on each side.
+    ///
+    /// The aspect ratio is kept, and images which already fit are re-encoded
+    /// at their own size rather than scaled up. `quality`, between `0.0` and
+    /// `1.0`, applies to lossy types such as `image/jpeg`. This draws to a
+    /// canvas, so it needs a document. Files the browser can't decode as an
+    /// image resolve to `FileReadError::InvalidImage`.
+    ///
+    /// ```no_run
+    /// # async fn example(photo: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let preview = photo.thumbnail_data_url(128, "image/jpeg", 0.8).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn thumbnail_data_url(
+        &self,
+        max_dim: u32,
+        mime: &str,
+        quality: f64,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let bitmap = self.decode_bitmap();
+        // Owned, so that the returned future doesn't borrow `mime`.
+        let mime = mime.to_string();
+
+        async move {
+            let bitmap = bitmap.await?;
+            let thumbnail = draw_thumbnail(&bitmap, max_dim, &mime, quality);
+            bitmap.close();
+            thumbnail.map_err(FileReadError::JsError)
+        }
+    }
+}
+
+impl File {
+    /// Decodes the file as an image and returns its pixels as `ImageData`,
+    /// in RGBA order at the image's own size.
+    ///
+    /// The image is drawn to an `OffscreenCanvas`, so this works in workers
+    /// as well as windows. Files the browser can't decode as an image resolve
+    /// to `FileReadError::InvalidImage`, and environments without
+    /// `OffscreenCanvas` to a `FileReadError::JsError` saying so.
+    ///
+    /// ```no_run
+    /// # async fn example(photo: gloo_file::File) -> Result<(), gloo_file::FileReadError> {
+    /// let pixels = photo.image_data().await?;
+    /// let rgba = pixels.data();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn image_data(&self) -> impl Future<Output = Result<web_sys::ImageData, FileReadError>> {
+        let bitmap = self.decode_bitmap();
+
+        async move {
+            let bitmap = bitmap.await?;
+            let data = draw_image_data(&bitmap);
+            bitmap.close();
+            data.map_err(FileReadError::JsError)
+        }
+    }
+}
+
+fn draw_image_data(bitmap: &web_sys::ImageBitmap) -> Result<web_sys::ImageData, JsValue> {
+    let supported = js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("OffscreenCanvas"))?;
+    if !supported {
+        return Err(Error::Unsupported("OffscreenCanvas").into());
+    }
+
+    let (width, height) = (bitmap.width(), bitmap.height());
+    let canvas = web_sys::OffscreenCanvas::new(width, height)?;
+    let context: web_sys::OffscreenCanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("the canvas has no 2D context"))?
+        .unchecked_into();
+    context.draw_image_with_image_bitmap(bitmap, 0.0, 0.0)?;
+    context.get_image_data(0.0, 0.0, f64::from(width), f64::from(height))
+}
+
+fn draw_thumbnail(
+    bitmap: &web_sys::ImageBitmap,
+    max_dim: u32,
+    mime: &str,
+    quality: f64,
+) -> Result<String, JsValue> {
+    let (width, height) = (bitmap.width(), bitmap.height());
+    let scale = (f64::from(max_dim) / f64::from(width.max(height).max(1))).min(1.0);
+    let width = (f64::from(width) * scale).round().max(1.0);
+    let height = (f64::from(height) * scale).round().max(1.0);
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document to draw the thumbnail in"))?;
+    let canvas: web_sys::HtmlCanvasElement = document.create_element("canvas")?.unchecked_into();
+    canvas.set_width(width as u32);
+    canvas.set_height(height as u32);
+    let context: web_sys::CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("the canvas has no 2D context"))?
+        .unchecked_into();
+    context.draw_image_with_image_bitmap_and_dw_and_dh(bitmap, 0.0, 0.0, width, height)?;
+    canvas.to_data_url_with_type_and_encoder_options(mime, &JsValue::from_f64(quality))
+}
+
+fn create_image_bitmap(blob: &web_sys::Blob) -> Result<js_sys::Promise, JsValue> {
+    // `createImageBitmap` is a global in both windows and workers.
+    let create: js_sys::Function =
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("createImageBitmap"))?
+            .dyn_into()?;
+    create.call1(&JsValue::UNDEFINED, blob)?.dyn_into()
+}
@@ -0,0 +1,1114 @@
+/*!
+
+Working with files and blobs on the Web.
+
+This crate wraps the [File API](https://w3c.github.io/FileAPI/): listing the
+files a user selected, creating blobs from in-memory data, and reading either
+of them asynchronously with a `FileReader`.
+
+## Reading a selected file
+
+```no_run
+use gloo_file::{FileList, FileReader};
+
+# async fn example(input: web_sys::HtmlInputElement) -> Result<(), gloo_file::FileReadError> {
+let files = FileList::new(&input).unwrap();
+let file = files.get(0).unwrap();
+
+let reader = FileReader::new().unwrap();
+
+let contents = reader.read_as_string(&file).await?;
+// Do something with the file's contents...
+# Ok(())
+# }
+```
+ */
+
+#![deny(missing_docs, missing_debug_implementations)]
+
+mod accept_list;
+mod blob_builder;
+mod blob_options;
+mod byte_size;
+#[cfg(feature = "canvas")]
+mod canvas;
+mod chunked_progress;
+mod constraints;
+#[cfg(feature = "csv")]
+mod csv;
+mod data_transfer;
+#[cfg(feature = "debug-leak-tracking")]
+pub mod debug;
+#[cfg(feature = "streams")]
+mod decompress;
+mod download;
+mod error;
+#[cfg(feature = "exif")]
+mod exif;
+mod file;
+mod file_cursor;
+mod file_list;
+mod form_data;
+#[cfg(feature = "fs_access")]
+mod fs_access;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "serde")]
+mod metadata;
+mod mime_type;
+#[cfg(feature = "mock")]
+pub mod mock;
+mod object_url;
+mod read_builder;
+mod read_file;
+mod read_seek;
+mod read_stats;
+mod reader_pool;
+
+pub use accept_list::AcceptList;
+pub use blob_builder::BlobBuilder;
+pub use blob_options::{BlobOptions, Endings};
+pub use byte_size::{humanize_bytes, ByteSize};
+#[cfg(feature = "canvas")]
+pub use canvas::file_from_canvas;
+pub use chunked_progress::ChunkedReadProgress;
+pub use constraints::{FileConstraints, FileValidationError};
+pub use data_transfer::add_to_data_transfer;
+#[cfg(feature = "streams")]
+pub use decompress::CompressionFormat;
+pub use download::{download, sanitize_filename};
+pub use error::{Error, FileReadError, Result};
+pub use file::{BlobChunk, File};
+pub use file_cursor::FileCursor;
+pub use file_list::{FileList, FileListIntoIter, FileListIter, ReadReport};
+pub use form_data::{append_blob_to_form_data, append_to_form_data};
+#[cfg(feature = "fs_access")]
+pub use fs_access::{files_in_directory, open_file_picker, FilePickerOptions, FilePickerType};
+#[cfg(feature = "serde")]
+pub use metadata::FileMetadata;
+pub use mime_type::{MimeCategory, MimeType};
+pub use object_url::{ObjectUrl, ObjectUrlSet};
+pub use read_builder::FileReadBuilder;
+pub use read_file::ReadFile;
+pub use read_seek::FileReadSeek;
+pub use read_stats::ReadStats;
+pub use reader_pool::FileReaderPool;
+
+use futures::channel::{mpsc, oneshot};
+use futures::future::{self, Either};
+use futures::stream::Stream;
+use gloo_timers::callback::Timeout;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
+use std::ops::Add;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+/// Blob-like data with a size and a type.
+pub trait Blob {
+    /// The size of the blob in bytes.
+    ///
+    /// This is a `u64` because blobs can be larger than 4 GB, which doesn't
+    /// fit in a `usize` on wasm32.
+    fn size(&self) -> u64;
+
+    /// The type string of the blob exactly as the browser reports it,
+    /// including any parameters such as `;charset=utf-8`.
+    fn raw_type(&self) -> String;
+
+    /// The MIME type of the blob, parsed from its
+    /// [`raw_type`](#tymethod.raw_type).
+    fn mime_type(&self) -> MimeType {
+        MimeType::from_raw(&self.raw_type())
+    }
+
+    /// Reads the blob as a UTF-8 string, like the web's
+    /// [`Blob.text()`](https://developer.mozilla.org/en-US/docs/Web/API/Blob/text).
+    fn text(&self) -> impl Future<Output = Result<String, FileReadError>>
+    where
+        Self: RawBlob + Sized,
+    {
+        read_once(FileReader::new().map(|reader| reader.read_as_string(self)))
+    }
+
+    /// Reads the blob as raw bytes, like the web's
+    /// [`Blob.arrayBuffer()`](https://developer.mozilla.org/en-US/docs/Web/API/Blob/arrayBuffer).
+    fn array_buffer(&self) -> impl Future<Output = Result<Vec<u8>, FileReadError>>
+    where
+        Self: RawBlob + Sized,
+    {
+        read_once(FileReader::new().map(|reader| reader.read_as_array_buffer(self)))
+    }
+}
+
+/// Types which are backed by a [`web_sys::Blob`](https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.Blob.html).
+pub trait RawBlob {
+    /// Returns the underlying `web_sys::Blob`.
+    fn raw(&self) -> &web_sys::Blob;
+
+    /// Returns a new blob containing the bytes from `start` up to, but not
+    /// including, `end`.
+    ///
+    /// Like the browser's `Blob.slice`, an `end` past the end of the blob is
+    /// clamped to its size, and a `start` after `end` yields an empty blob.
+    fn slice(&self, start: u64, end: u64) -> DataBlob {
+        let inner = self
+            .raw()
+            .slice_with_f64_and_f64(start as f64, end as f64)
+            .unwrap_throw();
+
+        DataBlob { inner }
+    }
+}
+
+/// A blob created from data held in memory.
+pub struct DataBlob {
+    inner: web_sys::Blob,
+}
+
+impl DataBlob {
+    /// Creates a new blob containing `content`.
+    ///
+    /// This fails only if the browser rejects the construction of the blob.
+    pub fn new(content: &str) -> Result<DataBlob> {
+        let parts = js_sys::Array::of1(&JsValue::from_str(content));
+        let inner = web_sys::Blob::new_with_str_sequence(&parts)?;
+
+        Ok(DataBlob { inner })
+    }
+
+    /// Creates a new blob containing `content`, whose type is `mime`.
+    ///
+    /// ```no_run
+    /// use gloo_file::{Blob, DataBlob, MimeType};
+    ///
+    /// let blob = DataBlob::new_with_type(r#"{"hello": "world"}"#, "application/json").unwrap();
+    ///
+    /// assert_eq!(blob.mime_type(), MimeType::ApplicationJson);
+    /// ```
+    pub fn new_with_type(content: &str, mime: &str) -> Result<DataBlob> {
+        let parts = js_sys::Array::of1(&JsValue::from_str(content));
+        let options = web_sys::BlobPropertyBag::new();
+        options.set_type(mime);
+        let inner = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options)?;
+
+        Ok(DataBlob { inner })
+    }
+
+    /// Creates a new blob containing `content`, with the given `options`.
+    pub fn new_with_options(content: &str, options: &BlobOptions) -> Result<DataBlob> {
+        let parts = js_sys::Array::of1(&JsValue::from_str(content));
+        let inner = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options.to_raw())?;
+
+        Ok(DataBlob { inner })
+    }
+
+    /// Creates a new blob containing a copy of `data`.
+    pub fn from_bytes(data: &[u8]) -> Result<DataBlob> {
+        let inner = web_sys::Blob::new_with_u8_array_sequence(&bytes_parts(data))?;
+
+        Ok(DataBlob { inner })
+    }
+
+    /// Creates a new blob containing a copy of `data`, whose type is `mime`.
+    pub fn from_bytes_with_type(data: &[u8], mime: &str) -> Result<DataBlob> {
+        let options = web_sys::BlobPropertyBag::new();
+        options.set_type(mime);
+        let inner =
+            web_sys::Blob::new_with_u8_array_sequence_and_options(&bytes_parts(data), &options)?;
+
+        Ok(DataBlob { inner })
+    }
+
+    /// The size of the blob in bytes, the same as
+    /// [`Blob::size`](trait.Blob.html#tymethod.size).
+    pub fn len(&self) -> u64 {
+        self.size()
+    }
+
+    /// Returns `true` if the blob holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if `other` wraps the very same JavaScript `Blob` object
+    /// as this blob, like [`File::same_object`](struct.File.html#method.same_object).
+    pub fn same_object(&self, other: &DataBlob) -> bool {
+        js_sys::Object::is(&self.inner, &other.inner)
+    }
+
+    /// Reads the blob as raw bytes.
+    ///
+    /// This is a shorthand for reading the blob with a new
+    /// [`FileReader`](struct.FileReader.html), like
+    /// [`File::bytes`](struct.File.html#method.bytes).
+    pub fn bytes(&self) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        read_once(FileReader::new().map(|reader| reader.read_as_array_buffer(self)))
+    }
+
+    /// Wraps a `web_sys::Blob` obtained from another web API, such as a
+    /// `fetch` response or `HTMLCanvasElement.toBlob`.
+    pub fn from_raw(inner: web_sys::Blob) -> DataBlob {
+        DataBlob { inner }
+    }
+}
+
+/// Wraps `data` in the sequence of parts the `Blob` constructor expects.
+fn bytes_parts(data: &[u8]) -> js_sys::Array {
+    // This is safe because the `Blob` constructor copies the bytes out of the
+    // view before anything else can run and reallocate wasm memory.
+    let view = unsafe { js_sys::Uint8Array::view(data) };
+    js_sys::Array::of1(&view)
+}
+
+impl fmt::Debug for DataBlob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DataBlob")
+            .field("size", &self.size())
+            .field("mime_type", &self.mime_type())
+            .finish()
+    }
+}
+
+/// Concatenates two blobs.
+///
+/// The result has the type of the two blobs if they share one, and no type
+/// otherwise.
+impl Add<&DataBlob> for &DataBlob {
+    type Output = DataBlob;
+
+    fn add(self, other: &DataBlob) -> DataBlob {
+        let parts = js_sys::Array::of2(&self.inner, &other.inner);
+        let options = web_sys::BlobPropertyBag::new();
+        let mime = self.raw_type();
+        if mime == other.raw_type() {
+            options.set_type(&mime);
+        }
+        let inner =
+            web_sys::Blob::new_with_blob_sequence_and_options(&parts, &options).unwrap_throw();
+
+        DataBlob { inner }
+    }
+}
+
+impl Add<&DataBlob> for DataBlob {
+    type Output = DataBlob;
+
+    fn add(self, other: &DataBlob) -> DataBlob {
+        &self + other
+    }
+}
+
+impl Add for DataBlob {
+    type Output = DataBlob;
+
+    fn add(self, other: DataBlob) -> DataBlob {
+        &self + &other
+    }
+}
+
+impl Blob for DataBlob {
+    fn size(&self) -> u64 {
+        self.inner.size() as u64
+    }
+
+    fn raw_type(&self) -> String {
+        self.inner.type_()
+    }
+}
+
+impl RawBlob for DataBlob {
+    fn raw(&self) -> &web_sys::Blob {
+        &self.inner
+    }
+}
+
+/// Reads the contents of blobs and files asynchronously.
+///
+/// A `FileReader` can be reused for any number of reads, but only one at a
+/// time: the browser's reader can't run two reads at once, so starting a read
+/// while another is still loading resolves the new read to an
+/// `InvalidStateError`, leaving the first to finish. Wait for each read to
+/// finish before starting the next, or use one reader per concurrent read.
+///
+/// Cloning a `FileReader` gives another handle to the same underlying reader.
+#[derive(Clone)]
+pub struct FileReader {
+    inner: web_sys::FileReader,
+    progress: Option<Rc<Progress>>,
+    timeout: Option<Duration>,
+    size_limit: Option<usize>,
+    signal: Option<web_sys::AbortSignal>,
+}
+
+/// Creates a new `FileReader`, like [`FileReader::new`](#method.new).
+///
+/// # Panics
+///
+/// Panics if the environment doesn't support `FileReader`, which only
+/// happens outside of browsers and workers.
+impl Default for FileReader {
+    fn default() -> FileReader {
+        FileReader::new().expect_throw("`FileReader` is not supported")
+    }
+}
+
+impl fmt::Debug for FileReader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileReader")
+            .field("ready_state", &self.ready_state())
+            .field("on_progress", &self.progress.is_some())
+            .field("timeout", &self.timeout)
+            .field("size_limit", &self.size_limit)
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
+
+impl FileReader {
+    /// Creates a new `FileReader`.
+    ///
+    /// This fails only if the environment doesn't support `FileReader`.
+    pub fn new() -> Result<FileReader> {
+        Ok(FileReader {
+            inner: web_sys::FileReader::new()?,
+            progress: None,
+            timeout: None,
+            size_limit: None,
+            signal: None,
+        })
+    }
+
+    /// Starts configuring a read on a new reader, for combining options such
+    /// as a text encoding, a size limit and a timeout.
+    pub fn builder() -> FileReadBuilder {
+        FileReadBuilder::new()
+    }
+
+    /// Calls `callback` with the fraction of the blob loaded so far, between
+    /// `0.0` and `1.0`, as the read progresses.
+    ///
+    /// The callback is skipped for progress events whose total length isn't
+    /// known, but it is always called with `1.0` once the read has loaded.
+    ///
+    /// ```no_run
+    /// use gloo_file::{DataBlob, FileReader};
+    ///
+    /// let mut reader = FileReader::new().unwrap();
+    /// reader.on_progress(|fraction| {
+    ///     // Update a progress bar...
+    /// });
+    ///
+    /// let contents = reader.read_as_string(&DataBlob::new("...").unwrap());
+    /// ```
+    pub fn on_progress<F>(&mut self, callback: F)
+    where
+        F: FnMut(f64) + 'static,
+    {
+        self.progress = Some(Rc::new(Progress {
+            callback: RefCell::new(Box::new(callback)),
+            last: Cell::new(0.0),
+        }));
+    }
+
+    /// Bounds every read to `timeout`.
+    ///
+    /// A read which hasn't finished when the timeout elapses is aborted, and
+    /// its future resolves to `FileReadError::TimedOut`. This guards against
+    /// browsers which occasionally never fire `load` or `error`.
+    ///
+    /// ```no_run
+    /// use gloo_file::{DataBlob, FileReader};
+    /// use std::time::Duration;
+    ///
+    /// let mut reader = FileReader::new().unwrap();
+    /// reader.set_timeout(Duration::from_secs(30));
+    ///
+    /// let contents = reader.read_as_string(&DataBlob::new("...").unwrap());
+    /// ```
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Refuses to read blobs larger than `max_bytes`.
+    ///
+    /// Reads of larger blobs resolve to `FileReadError::TooLarge` right away,
+    /// without the browser loading anything.
+    ///
+    /// ```no_run
+    /// use gloo_file::{DataBlob, FileReader};
+    ///
+    /// let mut reader = FileReader::new().unwrap();
+    /// reader.set_size_limit(10 * 1024 * 1024);
+    ///
+    /// let contents = reader.read_as_string(&DataBlob::new("...").unwrap());
+    /// ```
+    pub fn set_size_limit(&mut self, max_bytes: usize) {
+        self.size_limit = Some(max_bytes);
+    }
+
+    /// Aborts reads when `signal` is aborted, for cancelling them along with
+    /// other work through an `AbortController`.
+    ///
+    /// Reads started after the signal was aborted resolve to
+    /// `FileReadError::Aborted` right away.
+    ///
+    /// ```no_run
+    /// use gloo_file::{DataBlob, FileReader};
+    ///
+    /// let controller = web_sys::AbortController::new().unwrap();
+    /// let mut reader = FileReader::new().unwrap();
+    /// reader.set_signal(&controller.signal());
+    ///
+    /// let contents = reader.read_as_string(&DataBlob::new("...").unwrap());
+    /// controller.abort();
+    /// ```
+    pub fn set_signal(&mut self, signal: &web_sys::AbortSignal) {
+        self.signal = Some(signal.clone());
+    }
+
+    /// Aborts the read in progress, if there is one.
+    ///
+    /// The future of an aborted read resolves to `FileReadError::Aborted`.
+    /// Aborting a read which has already finished does nothing, and the
+    /// future resolves as it would have.
+    ///
+    /// ```no_run
+    /// use gloo_file::{DataBlob, FileReader};
+    ///
+    /// let reader = FileReader::new().unwrap();
+    /// let contents = reader.read_as_string(&DataBlob::new("...").unwrap());
+    ///
+    /// // Later, for example when the user navigates away:
+    /// reader.abort();
+    /// ```
+    pub fn abort(&self) {
+        self.inner.abort();
+    }
+
+    /// Resolves once the reader isn't `Loading`, so that a new read can be
+    /// started on it.
+    ///
+    /// This waits for the reader's `loadend` event rather than polling
+    /// [`ready_state`](#method.ready_state).
+    ///
+    /// ```no_run
+    /// # async fn example(reader: gloo_file::FileReader, blob: gloo_file::DataBlob) {
+    /// reader.when_idle().await;
+    /// let contents = reader.read_as_string(&blob).await;
+    /// # }
+    /// ```
+    pub fn when_idle(&self) -> impl Future<Output = ()> {
+        let (sender, receiver) = oneshot::channel();
+        if self.ready_state() == ReadyState::Loading {
+            // The listener frees itself once called, which `loadend`
+            // always is for a read in progress.
+            let onloadend = Closure::once_into_js(move || {
+                let _ = sender.send(());
+            });
+            let options = web_sys::AddEventListenerOptions::new();
+            options.set_once(true);
+            self.inner
+                .add_event_listener_with_callback_and_add_event_listener_options(
+                    "loadend",
+                    onloadend.unchecked_ref(),
+                    &options,
+                )
+                .unwrap_throw();
+        } else {
+            let _ = sender.send(());
+        }
+
+        async move {
+            let _ = receiver.await;
+        }
+    }
+
+    /// The exception the reader's last read failed with, if it failed.
+    ///
+    /// This gives the browser's own error name and message, for example to
+    /// tell a `NotFoundError` apart from a `NotReadableError`, which both
+    /// resolve to `FileReadError::NotReadable`.
+    pub fn last_error(&self) -> Option<web_sys::DomException> {
+        self.inner.error()
+    }
+
+    /// Whether the reader is idle, reading, or done with its last read.
+    ///
+    /// Only start a read on a reader that isn't `Loading`.
+    pub fn ready_state(&self) -> ReadyState {
+        match self.inner.ready_state() {
+            web_sys::FileReader::EMPTY => ReadyState::Empty,
+            web_sys::FileReader::LOADING => ReadyState::Loading,
+            _ => ReadyState::Done,
+        }
+    }
+
+    /// Reads `blob` as a UTF-8 string.
+    ///
+    /// The browser decodes the whole blob into a JavaScript string, which is
+    /// then re-encoded into a `String`. For text that isn't plain ASCII the
+    /// `String`'s size isn't known until it is encoded, so its buffer is
+    /// reallocated along the way; see
+    /// [`read_as_string_capacity`](#method.read_as_string_capacity) for large
+    /// files.
+    pub fn read_as_string(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.start_read(
+            blob,
+            |reader, blob| reader.read_as_text(blob),
+            |_| JsValue::from_str(""),
+            |result| result.as_string().unwrap_throw(),
+        )
+    }
+
+    /// Reads `blob` as a UTF-8 string, like
+    /// [`read_as_string`](#method.read_as_string), into a `String` allocated
+    /// up front at the blob's size.
+    ///
+    /// The blob's bytes are copied straight into the `String`'s buffer and
+    /// decoded there, so there are no reallocations however large the blob
+    /// is, and no JavaScript string is made. The text is decoded as
+    /// `read_as_string` would: a byte-order mark is removed and picks the
+    /// encoding, and invalid sequences are replaced by `U+FFFD`, which does
+    /// cost a second buffer.
+    pub fn read_as_string_capacity(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.start_read(
+            blob,
+            |reader, blob| reader.read_as_array_buffer(blob),
+            |_| js_sys::ArrayBuffer::new(0).into(),
+            utf8_string,
+        )
+    }
+
+    /// Reads `blob` as a string in the given `encoding`.
+    ///
+    /// `encoding` is any label the browser knows, like `"windows-1252"` or
+    /// `"shift_jis"`. Unknown labels fall back to UTF-8.
+    pub fn read_as_string_with_encoding(
+        &self,
+        blob: &(impl Blob + RawBlob),
+        encoding: &str,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        // Owned, so that the returned future doesn't borrow `encoding`.
+        let encoding = encoding.to_string();
+        self.start_read(
+            blob,
+            move |reader, blob| reader.read_as_text_with_label(blob, &encoding),
+            |_| JsValue::from_str(""),
+            |result| result.as_string().unwrap_throw(),
+        )
+    }
+
+    /// Reads `blob` as a "binary string", where each `char` holds one byte.
+    ///
+    /// The string is made of the code points `U+0000` to `U+00FF`, one per
+    /// byte, so its text is meaningless and bytes above `0x7F` take two bytes
+    /// in the string's UTF-8. This is only useful for passing data to older
+    /// JavaScript libraries which expect it; prefer
+    /// [`read_as_array_buffer`](#method.read_as_array_buffer) otherwise.
+    pub fn read_as_binary_string(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.start_read(
+            blob,
+            |reader, blob| reader.read_as_binary_string(blob),
+            |_| JsValue::from_str(""),
+            |result| result.as_string().unwrap_throw(),
+        )
+    }
+
+    /// Reads `blob` as raw bytes.
+    ///
+    /// An empty blob resolves to an empty `Vec`.
+    pub fn read_as_array_buffer(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        self.start_read(
+            blob,
+            |reader, blob| reader.read_as_array_buffer(blob),
+            |_| js_sys::ArrayBuffer::new(0).into(),
+            |result| js_sys::Uint8Array::new(&result).to_vec(),
+        )
+    }
+
+    /// Reads `blob` as raw bytes, like
+    /// [`read_as_array_buffer`](#method.read_as_array_buffer), along with a
+    /// stream of the read's progress.
+    ///
+    /// The stream yields an event for each of the browser's `progress`
+    /// events, and ends once the read does. The two can be polled together,
+    /// or one after the other.
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use gloo_file::{DataBlob, FileReader};
+    ///
+    /// # async fn example() -> Result<(), gloo_file::FileReadError> {
+    /// let reader = FileReader::new().unwrap();
+    /// let (bytes, progress) = reader.read_as_array_buffer_with_progress(&DataBlob::new("...").unwrap());
+    /// let report = progress.for_each(|event| async move {
+    ///     // Update a progress bar with `event.loaded`...
+    /// });
+    /// let (bytes, ()) = futures::join!(bytes, report);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_as_array_buffer_with_progress(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> (
+        impl Future<Output = Result<Vec<u8>, FileReadError>>,
+        impl Stream<Item = ProgressEvent>,
+    ) {
+        // A busy reader's events belong to the read already in progress.
+        let busy = self.ready_state() == ReadyState::Loading;
+        let read = self.read_as_array_buffer(blob);
+        let (sender, receiver) = mpsc::unbounded();
+        // Reads which settled straight away, like those of empty blobs, have
+        // no events, and dropping the sender ends the stream.
+        let listeners = if !busy && self.ready_state() == ReadyState::Loading {
+            Some(ProgressListeners::new(&self.inner, sender))
+        } else {
+            None
+        };
+
+        let read = async move {
+            let result = read.await;
+            drop(listeners);
+            result
+        };
+        (read, receiver)
+    }
+
+    /// Reads `blob` as a base64-encoded `data:` URL, suitable for use as the
+    /// `src` of an `<img>`.
+    ///
+    /// Blobs without a type are encoded as `application/octet-stream`.
+    pub fn read_as_data_url(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.start_read(
+            blob,
+            |reader, blob| reader.read_as_data_url(blob),
+            empty_data_url,
+            data_url,
+        )
+    }
+
+    /// Reads `blob` as whichever of the types above `kind` asks for.
+    ///
+    /// This is the same as calling the matching `read_as_*` method, for when
+    /// the type is only known at runtime.
+    ///
+    /// ```no_run
+    /// use gloo_file::{DataBlob, FileReader, ReadKind, ReadResult};
+    ///
+    /// # async fn example(kind: ReadKind) -> Result<(), gloo_file::FileReadError> {
+    /// let reader = FileReader::new().unwrap();
+    /// match reader.read(&DataBlob::new("...").unwrap(), kind).await? {
+    ///     ReadResult::Text(text) | ReadResult::DataUrl(text) => { /* ... */ }
+    ///     ReadResult::BinaryString(_) | ReadResult::ArrayBuffer(_) => { /* ... */ }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read(
+        &self,
+        blob: &(impl Blob + RawBlob),
+        kind: ReadKind,
+    ) -> impl Future<Output = Result<ReadResult, FileReadError>> {
+        self.start_read(
+            blob,
+            move |reader, blob| match kind {
+                ReadKind::Text => reader.read_as_text(blob),
+                ReadKind::ArrayBuffer => reader.read_as_array_buffer(blob),
+                ReadKind::DataUrl => reader.read_as_data_url(blob),
+                ReadKind::BinaryString => reader.read_as_binary_string(blob),
+            },
+            move |blob| match kind {
+                ReadKind::Text | ReadKind::BinaryString => JsValue::from_str(""),
+                ReadKind::ArrayBuffer => js_sys::ArrayBuffer::new(0).into(),
+                ReadKind::DataUrl => empty_data_url(blob),
+            },
+            move |result| match kind {
+                ReadKind::Text => ReadResult::Text(result.as_string().unwrap_throw()),
+                ReadKind::ArrayBuffer => {
+                    ReadResult::ArrayBuffer(js_sys::Uint8Array::new(&result).to_vec())
+                }
+                ReadKind::DataUrl => ReadResult::DataUrl(data_url(result)),
+                ReadKind::BinaryString => {
+                    ReadResult::BinaryString(result.as_string().unwrap_throw())
+                }
+            },
+        )
+    }
+
+    /// Reads `blob` with `start`, converting the reader's result with
+    /// `extract`.
+    ///
+    /// Empty blobs skip the browser and resolve straight away with `extract`
+    /// of what `empty` says the browser would have produced.
+    fn start_read<T, S, E, R>(
+        &self,
+        blob: &impl RawBlob,
+        start: S,
+        empty: E,
+        extract: R,
+    ) -> impl Future<Output = Result<T, FileReadError>>
+    where
+        S: FnOnce(&web_sys::FileReader, &web_sys::Blob) -> Result<(), JsValue>,
+        E: FnOnce(&web_sys::Blob) -> JsValue,
+        R: FnOnce(JsValue) -> T + 'static,
+        T: 'static,
+    {
+        if self.signal.as_ref().is_some_and(|signal| signal.aborted()) {
+            return Either::Left(future::ready(Err(FileReadError::Aborted)));
+        }
+        if blob.raw().size() == 0.0 {
+            if let Some(progress) = &self.progress {
+                progress.report(1.0);
+            }
+            return Either::Left(future::ready(Ok(extract(empty(blob.raw())))));
+        }
+        let too_large = self
+            .size_limit
+            .is_some_and(|limit| blob.raw().size() > limit as f64);
+        if too_large {
+            return Either::Left(future::ready(Err(FileReadError::TooLarge)));
+        }
+        // The handlers belong to the read in progress, so a read on a busy
+        // reader must fail before installing its own.
+        if self.ready_state() == ReadyState::Loading {
+            return Either::Left(future::ready(Err(busy_error())));
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        // `loadend` fires after every read, however it ends, so it is the one
+        // place the future is settled. The sender can only be taken once,
+        // which also lets the timer settle the future first.
+        let sender = Settle(Rc::new(RefCell::new(Some(sender))));
+
+        let onloadend = {
+            let sender = sender.clone();
+            let reader = self.inner.clone();
+            let progress = self.progress.clone();
+            Closure::once(move || {
+                sender.send(|| {
+                    if let Some(error) = reader.error() {
+                        return Err(FileReadError::from_js(error.into()));
+                    }
+                    // An aborted read has neither an error nor a result.
+                    let result = reader.result().unwrap_throw();
+                    if result.is_null() {
+                        return Err(FileReadError::Aborted);
+                    }
+                    if let Some(progress) = progress {
+                        progress.finish();
+                    }
+                    Ok(extract(result))
+                });
+            })
+        };
+        let onprogress = self.progress.clone().map(|progress| {
+            progress.last.set(0.0);
+            Closure::wrap(Box::new(move |event: web_sys::ProgressEvent| {
+                if event.length_computable() && event.total() > 0.0 {
+                    progress.report(event.loaded() / event.total());
+                }
+            }) as Box<dyn FnMut(web_sys::ProgressEvent)>)
+        });
+        self.inner
+            .set_onloadend(Some(onloadend.as_ref().unchecked_ref()));
+        self.inner.set_onprogress(
+            onprogress
+                .as_ref()
+                .map(|onprogress| onprogress.as_ref().unchecked_ref()),
+        );
+        if let Err(error) = start(&self.inner, blob.raw()) {
+            sender.send(|| Err(FileReadError::from_js(error)));
+        }
+        // The timer claims the sender before aborting, so the `loadend` event
+        // it causes is ignored. It is cancelled when the future completes.
+        let timer = self.timeout.map(|timeout| {
+            let sender = sender.clone();
+            let reader = self.inner.clone();
+            // `setTimeout` treats delays beyond `i32::MAX` as zero.
+            let millis = timeout.as_millis().min(i32::MAX as u128) as u32;
+            Timeout::new(millis, move || {
+                sender.send(|| Err(FileReadError::TimedOut));
+                reader.abort();
+            })
+        });
+        let signal = self.signal.clone().map(|signal| {
+            let reader = self.inner.clone();
+            // Aborting the reader settles the future through its `loadend`
+            // event, unless the read has already finished.
+            let onabort = Closure::once(move || reader.abort());
+            signal
+                .add_event_listener_with_callback("abort", onabort.as_ref().unchecked_ref())
+                .unwrap_throw();
+            (signal, onabort)
+        });
+
+        let handlers = ReadHandlers {
+            reader: self.inner.clone(),
+            settle: sender,
+            _onloadend: onloadend,
+            _onprogress: onprogress,
+            _timer: timer,
+            signal,
+        };
+
+        // The future owns the handlers, so they are freed along with it
+        // instead of being leaked, even if it is dropped mid-read.
+        Either::Right(async move {
+            let result = receiver.await;
+            drop(handlers);
+            // The sender is only dropped without sending if the reader was
+            // garbage collected mid-read, which we treat as an abort.
+            result.unwrap_or(Err(FileReadError::Aborted))
+        })
+    }
+}
+
+/// How far a read has got, as reported by
+/// [`FileReader::read_as_array_buffer_with_progress`](struct.FileReader.html#method.read_as_array_buffer_with_progress).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressEvent {
+    /// The number of bytes loaded so far.
+    pub loaded: u64,
+    /// The size of the blob in bytes, if the browser knows it.
+    pub total: Option<u64>,
+}
+
+/// The listeners forwarding a read's progress to a stream, which are removed
+/// when dropped.
+struct ProgressListeners {
+    reader: web_sys::FileReader,
+    onprogress: Closure<dyn FnMut(web_sys::ProgressEvent)>,
+    onloadend: Closure<dyn FnMut()>,
+}
+
+impl ProgressListeners {
+    fn new(reader: &web_sys::FileReader, sender: mpsc::UnboundedSender<ProgressEvent>) -> Self {
+        let onprogress = {
+            let sender = sender.clone();
+            Closure::wrap(Box::new(move |event: web_sys::ProgressEvent| {
+                let _ = sender.unbounded_send(ProgressEvent {
+                    loaded: event.loaded() as u64,
+                    total: Some(event.total() as u64).filter(|_| event.length_computable()),
+                });
+            }) as Box<dyn FnMut(web_sys::ProgressEvent)>)
+        };
+        // Ends the stream even if the read's future isn't polled again.
+        let onloadend = Closure::wrap(Box::new(move || sender.close_channel()) as Box<dyn FnMut()>);
+        for (event, listener) in [
+            ("progress", onprogress.as_ref()),
+            ("loadend", onloadend.as_ref()),
+        ] {
+            reader
+                .add_event_listener_with_callback(event, listener.unchecked_ref())
+                .unwrap_throw();
+        }
+
+        ProgressListeners {
+            reader: reader.clone(),
+            onprogress,
+            onloadend,
+        }
+    }
+}
+
+impl Drop for ProgressListeners {
+    fn drop(&mut self) {
+        for (event, listener) in [
+            ("progress", self.onprogress.as_ref()),
+            ("loadend", self.onloadend.as_ref()),
+        ] {
+            let _ = self
+                .reader
+                .remove_event_listener_with_callback(event, listener.unchecked_ref());
+        }
+    }
+}
+
+/// What the browser gives for reading an empty blob as a data URL.
+fn empty_data_url(blob: &web_sys::Blob) -> JsValue {
+    JsValue::from_str(&format!("data:{};base64,", blob.type_()))
+}
+
+/// Decodes the result of `readAsArrayBuffer` as `readAsText` would, in a
+/// buffer allocated once at its size.
+fn utf8_string(result: JsValue) -> String {
+    let view = js_sys::Uint8Array::new(&result);
+    let mut bytes = vec![0; view.length() as usize];
+    view.copy_to(&mut bytes);
+
+    match bytes.as_slice() {
+        [0xef, 0xbb, 0xbf, ..] => {
+            bytes.drain(..3);
+        }
+        [0xff, 0xfe, ..] | [0xfe, 0xff, ..] => return file::decode_with_bom(&bytes),
+        _ => {}
+    }
+    String::from_utf8(bytes)
+        .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned())
+}
+
+/// Extracts the data URL from the result of `readAsDataURL`.
+fn data_url(result: JsValue) -> String {
+    let url = result.as_string().unwrap_throw();
+    // Some browsers leave the type out entirely for untyped blobs.
+    match url.strip_prefix("data:;") {
+        Some(rest) => format!("data:application/octet-stream;{}", rest),
+        None => url,
+    }
+}
+
+/// The event handlers of a read in progress.
+///
+/// When dropped, an unsettled read is aborted and the handlers are removed
+/// from the reader, so the browser never calls a freed closure.
+struct ReadHandlers<T> {
+    reader: web_sys::FileReader,
+    settle: Settle<T>,
+    // Only held to keep them alive, and freed once the read is done.
+    _onloadend: Closure<dyn FnMut()>,
+    _onprogress: Option<Closure<dyn FnMut(web_sys::ProgressEvent)>>,
+    _timer: Option<Timeout>,
+    signal: Option<(web_sys::AbortSignal, Closure<dyn FnMut()>)>,
+}
+
+impl<T> Drop for ReadHandlers<T> {
+    fn drop(&mut self) {
+        if self.settle.is_pending() {
+            // `abort` fires its events synchronously, while the handlers
+            // are still alive.
+            self.reader.abort();
+        }
+        self.reader.set_onloadend(None);
+        self.reader.set_onprogress(None);
+        if let Some((signal, onabort)) = &self.signal {
+            let _ = signal
+                .remove_event_listener_with_callback("abort", onabort.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// The error the browser throws when starting a read on a busy reader.
+fn busy_error() -> FileReadError {
+    let exception = web_sys::DomException::new_with_message_and_name(
+        "the reader is already reading a blob",
+        "InvalidStateError",
+    );
+    FileReadError::JsError(exception.map_or_else(|error| error, JsValue::from))
+}
+
+/// The state of a [`FileReader`](struct.FileReader.html), like the DOM's
+/// [`FileReader.readyState`](https://developer.mozilla.org/en-US/docs/Web/API/FileReader/readyState).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadyState {
+    /// No read has been started yet.
+    Empty,
+    /// A read is in progress.
+    Loading,
+    /// The last read finished, whether it succeeded, failed or was aborted.
+    Done,
+}
+
+/// The type to read a blob as with [`FileReader::read`](struct.FileReader.html#method.read).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadKind {
+    /// A UTF-8 string, as with `read_as_string`.
+    Text,
+    /// Raw bytes, as with `read_as_array_buffer`.
+    ArrayBuffer,
+    /// A `data:` URL, as with `read_as_data_url`.
+    DataUrl,
+    /// A "binary string", as with `read_as_binary_string`.
+    BinaryString,
+}
+
+/// The contents of a blob read with [`FileReader::read`](struct.FileReader.html#method.read),
+/// in the variant matching the [`ReadKind`](enum.ReadKind.html) it was read as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadResult {
+    /// The blob read as `ReadKind::Text`.
+    Text(String),
+    /// The blob read as `ReadKind::ArrayBuffer`.
+    ArrayBuffer(Vec<u8>),
+    /// The blob read as `ReadKind::DataUrl`.
+    DataUrl(String),
+    /// The blob read as `ReadKind::BinaryString`.
+    BinaryString(String),
+}
+
+/// Flattens a read started on a new `FileReader`, resolving to an error if
+/// the reader couldn't be created.
+async fn read_once<T, R>(read: Result<R>) -> Result<T, FileReadError>
+where
+    R: Future<Output = Result<T, FileReadError>>,
+{
+    read.map_err(Error::into_read_error)?.await
+}
+
+/// A progress callback registered with `FileReader::on_progress`.
+struct Progress {
+    callback: RefCell<Box<dyn FnMut(f64)>>,
+    last: Cell<f64>,
+}
+
+impl Progress {
+    fn report(&self, fraction: f64) {
+        self.last.set(fraction);
+        (self.callback.borrow_mut())(fraction);
+    }
+
+    /// Reports completion, unless the last progress event already did.
+    fn finish(&self) {
+        if self.last.get() < 1.0 {
+            self.report(1.0);
+        }
+    }
+}
+
+/// The sending half of a read's result, shared between the reader's event
+/// handlers so that only the first one to fire is heard.
+struct Settle<T>(Rc<RefCell<Option<ReadSender<T>>>>);
+
+type ReadSender<T> = oneshot::Sender<Result<T, FileReadError>>;
+
+impl<T> Clone for Settle<T> {
+    fn clone(&self) -> Self {
+        Settle(self.0.clone())
+    }
+}
+
+impl<T> Settle<T> {
+    /// Whether nothing has settled the read yet.
+    fn is_pending(&self) -> bool {
+        self.0.borrow().is_some()
+    }
+
+    fn send<F>(&self, result: F)
+    where
+        F: FnOnce() -> Result<T, FileReadError>,
+    {
+        if let Some(sender) = self.0.borrow_mut().take() {
+            // The receiver is only gone if the future was dropped.
+            let _ = sender.send(result());
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{Blob, File};
+use serde::{Deserialize, Serialize};
+
+/// A description of a [`File`](struct.File.html) without its contents, for
+/// sending to a server or logging.
+///
+/// Unlike a `File`, this is plain data, so it can be created, serialized and
+/// compared outside of a browser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileMetadata {
+    /// The name of the file, without any path information.
+    pub name: String,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The MIME type of the file, or the empty string if the browser didn't
+    /// report one.
+    pub mime_type: String,
+    /// When the file was last modified, in milliseconds since the Unix epoch.
+    pub last_modified: f64,
+    /// The path of the file within the directory the user selected, if any.
+    pub relative_path: Option<String>,
+}
+
+impl File {
+    /// Describes the file, without reading it.
+    pub fn metadata(&self) -> FileMetadata {
+        FileMetadata {
+            name: self.name().to_string(),
+            size: self.size(),
+            mime_type: self.raw_type(),
+            last_modified: self.last_modified(),
+            relative_path: self.relative_path(),
+        }
+    }
+}
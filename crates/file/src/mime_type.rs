@@ -0,0 +1,379 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The MIME type of a blob, as reported by the browser.
+///
+/// Two `Other` types are equal when their strings are.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MimeType {
+    /// The browser didn't report a type.
+    Unknown,
+
+    /// `text/plain`
+    TextPlain,
+    /// `text/html`
+    TextHtml,
+    /// `text/css`
+    TextCss,
+    /// `text/csv`
+    TextCsv,
+    /// `text/javascript`
+    TextJavascript,
+
+    /// `application/json`
+    ApplicationJson,
+    /// `application/pdf`
+    ApplicationPdf,
+    /// `application/xml`
+    ApplicationXml,
+    /// `application/zip`
+    ApplicationZip,
+    /// `application/octet-stream`
+    ApplicationOctetStream,
+
+    /// `image/png`
+    ImagePng,
+    /// `image/jpeg`
+    ImageJpeg,
+    /// `image/gif`
+    ImageGif,
+    /// `image/svg+xml`
+    ImageSvg,
+    /// `image/webp`
+    ImageWebp,
+
+    /// `audio/mpeg`
+    AudioMpeg,
+    /// `audio/ogg`
+    AudioOgg,
+    /// `audio/wav`
+    AudioWav,
+
+    /// `video/mp4`
+    VideoMp4,
+    /// `video/webm`
+    VideoWebm,
+
+    /// Any other type, holding the type string exactly as the browser reported it.
+    Other(String),
+}
+
+impl MimeType {
+    /// Returns the canonical MIME string of the type, e.g. `"application/json"`.
+    ///
+    /// `Unknown` is the empty string, and `Other` is its string unchanged.
+    pub fn as_str(&self) -> &str {
+        match self {
+            MimeType::Unknown => "",
+            MimeType::TextPlain => "text/plain",
+            MimeType::TextHtml => "text/html",
+            MimeType::TextCss => "text/css",
+            MimeType::TextCsv => "text/csv",
+            MimeType::TextJavascript => "text/javascript",
+            MimeType::ApplicationJson => "application/json",
+            MimeType::ApplicationPdf => "application/pdf",
+            MimeType::ApplicationXml => "application/xml",
+            MimeType::ApplicationZip => "application/zip",
+            MimeType::ApplicationOctetStream => "application/octet-stream",
+            MimeType::ImagePng => "image/png",
+            MimeType::ImageJpeg => "image/jpeg",
+            MimeType::ImageGif => "image/gif",
+            MimeType::ImageSvg => "image/svg+xml",
+            MimeType::ImageWebp => "image/webp",
+            MimeType::AudioMpeg => "audio/mpeg",
+            MimeType::AudioOgg => "audio/ogg",
+            MimeType::AudioWav => "audio/wav",
+            MimeType::VideoMp4 => "video/mp4",
+            MimeType::VideoWebm => "video/webm",
+            MimeType::Other(raw) => raw,
+        }
+    }
+
+    /// Returns `true` if the type matches `pattern`, in the syntax of an
+    /// `<input type="file">`'s `accept` attribute.
+    ///
+    /// `pattern` is a comma-separated list of exact types, like `text/csv`,
+    /// or wildcards, like `image/*` and `*`. Types are matched without their
+    /// parameters and regardless of case. File extensions such as `.png` can
+    /// also appear in `accept`, but never match a type.
+    ///
+    /// ```
+    /// use gloo_file::MimeType;
+    ///
+    /// assert!(MimeType::ImagePng.matches_pattern("image/*"));
+    /// assert!(MimeType::TextCsv.matches_pattern("application/json, text/csv"));
+    /// assert!(!MimeType::TextCsv.matches_pattern(".csv"));
+    /// ```
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        let essence = essence(self.as_str());
+
+        pattern.split(',').map(str::trim).any(|pattern| {
+            let pattern = pattern.to_ascii_lowercase();
+            match pattern.as_str() {
+                "*" | "*/*" => true,
+                _ if pattern.ends_with("/*") => essence.starts_with(&pattern[..pattern.len() - 1]),
+                _ => !essence.is_empty() && essence == pattern,
+            }
+        })
+    }
+
+    /// Returns the broad category of the type, from its top-level type.
+    ///
+    /// `Other` types are categorized by the part before their `/` too, so
+    /// `image/avif` is an `Image`. Unknown types are `MimeCategory::Other`.
+    ///
+    /// ```
+    /// use gloo_file::{MimeCategory, MimeType};
+    ///
+    /// assert_eq!(MimeType::ImagePng.category(), MimeCategory::Image);
+    /// assert_eq!("audio/flac".parse::<MimeType>().unwrap().category(), MimeCategory::Audio);
+    /// ```
+    pub fn category(&self) -> MimeCategory {
+        match essence(self.as_str()).split('/').next() {
+            Some("image") => MimeCategory::Image,
+            Some("audio") => MimeCategory::Audio,
+            Some("video") => MimeCategory::Video,
+            Some("text") => MimeCategory::Text,
+            Some("application") => MimeCategory::Application,
+            _ => MimeCategory::Other,
+        }
+    }
+
+    /// Returns the usual file extension for the type, without the dot, e.g.
+    /// `"json"` for `ApplicationJson`.
+    ///
+    /// A few common `Other` types, like `image/avif`, are recognized by their
+    /// essence too. This is `None` for `Unknown` and for other unrecognized
+    /// types.
+    ///
+    /// ```
+    /// use gloo_file::MimeType;
+    ///
+    /// assert_eq!(MimeType::ImageJpeg.preferred_extension(), Some("jpg"));
+    /// let name = format!("report.{}", MimeType::TextCsv.preferred_extension().unwrap());
+    /// assert_eq!(name, "report.csv");
+    /// ```
+    pub fn preferred_extension(&self) -> Option<&'static str> {
+        let extension = match self {
+            MimeType::Unknown => return None,
+            MimeType::TextPlain => "txt",
+            MimeType::TextHtml => "html",
+            MimeType::TextCss => "css",
+            MimeType::TextCsv => "csv",
+            MimeType::TextJavascript => "js",
+            MimeType::ApplicationJson => "json",
+            MimeType::ApplicationPdf => "pdf",
+            MimeType::ApplicationXml => "xml",
+            MimeType::ApplicationZip => "zip",
+            MimeType::ApplicationOctetStream => "bin",
+            MimeType::ImagePng => "png",
+            MimeType::ImageJpeg => "jpg",
+            MimeType::ImageGif => "gif",
+            MimeType::ImageSvg => "svg",
+            MimeType::ImageWebp => "webp",
+            MimeType::AudioMpeg => "mp3",
+            MimeType::AudioOgg => "ogg",
+            MimeType::AudioWav => "wav",
+            MimeType::VideoMp4 => "mp4",
+            MimeType::VideoWebm => "webm",
+            MimeType::Other(raw) => match essence(raw).as_str() {
+                "text/markdown" => "md",
+                "application/gzip" => "gz",
+                "application/wasm" => "wasm",
+                "image/avif" => "avif",
+                "image/bmp" => "bmp",
+                "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+                "audio/flac" => "flac",
+                "font/woff" => "woff",
+                "font/woff2" => "woff2",
+                _ => return None,
+            },
+        };
+
+        Some(extension)
+    }
+
+    /// Guesses the type of a file from its extension, without the dot, e.g.
+    /// `TextCsv` for `"csv"`.
+    ///
+    /// Extensions are matched regardless of case. This returns `None` for
+    /// unrecognized extensions.
+    ///
+    /// ```
+    /// use gloo_file::MimeType;
+    ///
+    /// assert_eq!(MimeType::from_extension("JPEG"), Some(MimeType::ImageJpeg));
+    /// assert_eq!(MimeType::from_extension("unheard-of"), None);
+    /// ```
+    pub fn from_extension(extension: &str) -> Option<MimeType> {
+        let mime = match extension.to_ascii_lowercase().as_str() {
+            "txt" | "text" => MimeType::TextPlain,
+            "html" | "htm" => MimeType::TextHtml,
+            "css" => MimeType::TextCss,
+            "csv" => MimeType::TextCsv,
+            "js" | "mjs" => MimeType::TextJavascript,
+            "json" => MimeType::ApplicationJson,
+            "pdf" => MimeType::ApplicationPdf,
+            "xml" => MimeType::ApplicationXml,
+            "zip" => MimeType::ApplicationZip,
+            "bin" => MimeType::ApplicationOctetStream,
+            "png" => MimeType::ImagePng,
+            "jpg" | "jpeg" => MimeType::ImageJpeg,
+            "gif" => MimeType::ImageGif,
+            "svg" => MimeType::ImageSvg,
+            "webp" => MimeType::ImageWebp,
+            "mp3" => MimeType::AudioMpeg,
+            "ogg" | "oga" => MimeType::AudioOgg,
+            "wav" => MimeType::AudioWav,
+            "mp4" | "m4v" => MimeType::VideoMp4,
+            "webm" => MimeType::VideoWebm,
+            "md" | "markdown" => MimeType::Other("text/markdown".to_string()),
+            "gz" => MimeType::Other("application/gzip".to_string()),
+            "wasm" => MimeType::Other("application/wasm".to_string()),
+            "avif" => MimeType::Other("image/avif".to_string()),
+            "bmp" => MimeType::Other("image/bmp".to_string()),
+            "ico" => MimeType::Other("image/x-icon".to_string()),
+            "flac" => MimeType::Other("audio/flac".to_string()),
+            "woff" => MimeType::Other("font/woff".to_string()),
+            "woff2" => MimeType::Other("font/woff2".to_string()),
+            _ => return None,
+        };
+
+        Some(mime)
+    }
+
+    /// Detects the type of a file from the magic number in its first bytes.
+    ///
+    /// `bytes` should be at least the first 12 bytes of the file; fewer may
+    /// miss some types. This returns `None` for unrecognized formats.
+    ///
+    /// ```
+    /// use gloo_file::MimeType;
+    ///
+    /// let header = b"%PDF-1.7";
+    /// assert_eq!(MimeType::from_magic_bytes(header), Some(MimeType::ApplicationPdf));
+    /// ```
+    pub fn from_magic_bytes(bytes: &[u8]) -> Option<MimeType> {
+        let riff = |kind: &[u8]| bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(kind);
+
+        let mime = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            MimeType::ImagePng
+        } else if bytes.starts_with(b"\xff\xd8\xff") {
+            MimeType::ImageJpeg
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            MimeType::ImageGif
+        } else if riff(b"WEBP") {
+            MimeType::ImageWebp
+        } else if bytes.starts_with(b"%PDF-") {
+            MimeType::ApplicationPdf
+        } else if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+            MimeType::ApplicationZip
+        } else if bytes.starts_with(b"ID3") {
+            MimeType::AudioMpeg
+        } else if bytes.starts_with(b"OggS") {
+            MimeType::AudioOgg
+        } else if riff(b"WAVE") {
+            MimeType::AudioWav
+        } else if bytes.get(4..8) == Some(b"ftyp") {
+            return bytes.get(8..12).and_then(from_iso_brand);
+        } else if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+            MimeType::VideoWebm
+        } else {
+            return None;
+        };
+
+        Some(mime)
+    }
+
+    /// Parses the `type` string of a blob.
+    ///
+    /// Parameters such as `;charset=utf-8` are ignored when matching known
+    /// types, but are kept in `Other`.
+    pub(crate) fn from_raw(raw: &str) -> MimeType {
+        match essence(raw).as_str() {
+            "" => MimeType::Unknown,
+            "text/plain" => MimeType::TextPlain,
+            "text/html" => MimeType::TextHtml,
+            "text/css" => MimeType::TextCss,
+            "text/csv" => MimeType::TextCsv,
+            "text/javascript" | "application/javascript" => MimeType::TextJavascript,
+            "application/json" => MimeType::ApplicationJson,
+            "application/pdf" => MimeType::ApplicationPdf,
+            "application/xml" | "text/xml" => MimeType::ApplicationXml,
+            "application/zip" => MimeType::ApplicationZip,
+            "application/octet-stream" => MimeType::ApplicationOctetStream,
+            "image/png" => MimeType::ImagePng,
+            "image/jpeg" => MimeType::ImageJpeg,
+            "image/gif" => MimeType::ImageGif,
+            "image/svg+xml" => MimeType::ImageSvg,
+            "image/webp" => MimeType::ImageWebp,
+            "audio/mpeg" => MimeType::AudioMpeg,
+            "audio/ogg" => MimeType::AudioOgg,
+            "audio/wav" | "audio/x-wav" => MimeType::AudioWav,
+            "video/mp4" => MimeType::VideoMp4,
+            "video/webm" => MimeType::VideoWebm,
+            _ => MimeType::Other(raw.to_string()),
+        }
+    }
+}
+
+/// The top-level type of a [`MimeType`](enum.MimeType.html), for grouping
+/// files by kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MimeCategory {
+    /// `image/*`
+    Image,
+    /// `audio/*`
+    Audio,
+    /// `video/*`
+    Video,
+    /// `text/*`
+    Text,
+    /// `application/*`
+    Application,
+    /// Any other top-level type, like `font/*`, or no type at all.
+    Other,
+}
+
+/// Maps the major brand of an ISO base media file, from its `ftyp` box, to
+/// the file's type.
+///
+/// MP4 video, M4A audio and HEIF and AVIF images all share the container, so
+/// only the brand tells them apart.
+fn from_iso_brand(brand: &[u8]) -> Option<MimeType> {
+    let mime = match brand {
+        b"isom" | b"iso2" | b"iso4" | b"iso5" | b"iso6" | b"mp41" | b"mp42" | b"avc1" | b"dash"
+        | b"M4V " => MimeType::VideoMp4,
+        b"M4A " | b"M4B " => MimeType::Other("audio/mp4".to_string()),
+        b"heic" | b"heix" | b"heim" | b"heis" => MimeType::Other("image/heic".to_string()),
+        b"mif1" | b"msf1" => MimeType::Other("image/heif".to_string()),
+        b"avif" | b"avis" => MimeType::Other("image/avif".to_string()),
+        _ => return None,
+    };
+
+    Some(mime)
+}
+
+/// The type and subtype of a MIME string, lowercased and without parameters.
+fn essence(raw: &str) -> String {
+    raw.split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Parsing never fails: strings which aren't a known type become `Other`.
+impl FromStr for MimeType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(MimeType::from_raw(s))
+    }
+}
+
+impl fmt::Display for MimeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,430 @@
+//! Pure-Rust stand-ins for [`DataBlob`](../struct.DataBlob.html) and
+//! [`FileReader`](../struct.FileReader.html), behind the `mock` feature, for
+//! testing code which reads blobs with a plain `cargo test`.
+//!
+//! The types here have the same names and methods as the browser-backed
+//! ones, and produce the same results, so code written against them can
+//! switch backends with a `use`:
+//!
+//! ```
+//! use gloo_file::mock::{DataBlob, FileReader};
+//! use gloo_file::{Blob, MimeType};
+//!
+//! # futures::executor::block_on(async {
+//! let blob = DataBlob::new_with_type("name,size\n", "text/csv").unwrap();
+//! assert_eq!(blob.mime_type(), MimeType::TextCsv);
+//!
+//! let reader = FileReader::new().unwrap();
+//! let header = reader.read_as_string(&blob.slice(0, 4)).await.unwrap();
+//! assert_eq!(header, "name");
+//! # });
+//! ```
+//!
+//! Code generic over [`ReadFile`](../trait.ReadFile.html) works with either
+//! reader. Only what can be done without a browser is mirrored, so there is
+//! nothing like `as_raw` for getting at a `web_sys` object, and the mock
+//! `DataBlob` isn't a [`RawBlob`](../trait.RawBlob.html).
+
+use crate::file::{decode_utf16, decode_with_bom, encode_base64};
+use crate::{Blob, FileReadError, ReadFile, ReadyState, Result};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::{self, Future};
+use std::ops::Add;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A blob held in Rust memory, mirroring
+/// [`gloo_file::DataBlob`](../struct.DataBlob.html).
+pub struct DataBlob {
+    data: Rc<[u8]>,
+    start: usize,
+    end: usize,
+    mime: String,
+}
+
+impl DataBlob {
+    /// Creates a new blob containing `content`.
+    pub fn new(content: &str) -> Result<DataBlob> {
+        DataBlob::from_bytes(content.as_bytes())
+    }
+
+    /// Creates a new blob containing `content`, whose type is `mime`.
+    pub fn new_with_type(content: &str, mime: &str) -> Result<DataBlob> {
+        DataBlob::from_bytes_with_type(content.as_bytes(), mime)
+    }
+
+    /// Creates a new blob containing a copy of `data`.
+    pub fn from_bytes(data: &[u8]) -> Result<DataBlob> {
+        DataBlob::from_bytes_with_type(data, "")
+    }
+
+    /// Creates a new blob containing a copy of `data`, whose type is `mime`.
+    ///
+    /// As in the browser, the type is lowercased, and dropped if it has any
+    /// characters outside printable ASCII.
+    pub fn from_bytes_with_type(data: &[u8], mime: &str) -> Result<DataBlob> {
+        let mime = if mime.chars().all(|c| (' '..='~').contains(&c)) {
+            mime.to_ascii_lowercase()
+        } else {
+            String::new()
+        };
+
+        Ok(DataBlob {
+            data: data.into(),
+            start: 0,
+            end: data.len(),
+            mime,
+        })
+    }
+
+    /// The size of the blob in bytes, the same as
+    /// [`Blob::size`](../trait.Blob.html#tymethod.size).
+    pub fn len(&self) -> u64 {
+        self.size()
+    }
+
+    /// Returns `true` if the blob holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if `other` is a slice of the same bytes, over the same
+    /// range, as this blob.
+    pub fn same_object(&self, other: &DataBlob) -> bool {
+        Rc::ptr_eq(&self.data, &other.data) && self.start == other.start && self.end == other.end
+    }
+
+    /// Returns a new blob containing the bytes from `start` up to, but not
+    /// including, `end`, clamped like
+    /// [`RawBlob::slice`](../trait.RawBlob.html#method.slice).
+    ///
+    /// The bytes are shared rather than copied, and the slice has no type.
+    pub fn slice(&self, start: u64, end: u64) -> DataBlob {
+        let start = start.min(self.len()) as usize;
+        let end = end.clamp(start as u64, self.len()) as usize;
+
+        DataBlob {
+            data: self.data.clone(),
+            start: self.start + start,
+            end: self.start + end,
+            mime: String::new(),
+        }
+    }
+
+    /// Reads the blob as raw bytes.
+    pub fn bytes(&self) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        future::ready(Ok(self.contents().to_vec()))
+    }
+
+    /// Reads the blob as a UTF-8 string, like
+    /// [`Blob::text`](../trait.Blob.html#method.text).
+    pub fn text(&self) -> impl Future<Output = Result<String, FileReadError>> {
+        future::ready(Ok(decode_with_bom(self.contents())))
+    }
+
+    /// Reads the blob as raw bytes, like
+    /// [`Blob::array_buffer`](../trait.Blob.html#method.array_buffer).
+    pub fn array_buffer(&self) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        self.bytes()
+    }
+
+    fn contents(&self) -> &[u8] {
+        &self.data[self.start..self.end]
+    }
+}
+
+impl Blob for DataBlob {
+    fn size(&self) -> u64 {
+        (self.end - self.start) as u64
+    }
+
+    fn raw_type(&self) -> String {
+        self.mime.clone()
+    }
+}
+
+impl fmt::Debug for DataBlob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DataBlob")
+            .field("size", &self.size())
+            .field("mime_type", &self.mime_type())
+            .finish()
+    }
+}
+
+/// Concatenates two blobs.
+///
+/// The result has the type of the two blobs if they share one, and no type
+/// otherwise.
+impl Add<&DataBlob> for &DataBlob {
+    type Output = DataBlob;
+
+    fn add(self, other: &DataBlob) -> DataBlob {
+        let data = [self.contents(), other.contents()].concat();
+        let mime = if self.mime == other.mime {
+            self.mime.clone()
+        } else {
+            String::new()
+        };
+
+        DataBlob {
+            end: data.len(),
+            data: data.into(),
+            start: 0,
+            mime,
+        }
+    }
+}
+
+impl Add<&DataBlob> for DataBlob {
+    type Output = DataBlob;
+
+    fn add(self, other: &DataBlob) -> DataBlob {
+        &self + other
+    }
+}
+
+impl Add for DataBlob {
+    type Output = DataBlob;
+
+    fn add(self, other: DataBlob) -> DataBlob {
+        &self + &other
+    }
+}
+
+/// Reads [`DataBlob`](struct.DataBlob.html)s, mirroring
+/// [`gloo_file::FileReader`](../struct.FileReader.html), and implementing
+/// [`ReadFile`](../trait.ReadFile.html) for them.
+///
+/// Every read is done as soon as it is started, so the reader is never
+/// `Loading`, a timeout never elapses and there is nothing to abort. Cloning
+/// a `FileReader` gives another handle to the same reader.
+#[derive(Clone)]
+pub struct FileReader {
+    ready_state: Rc<Cell<ReadyState>>,
+    progress: Option<Rc<RefCell<ProgressCallback>>>,
+    timeout: Option<Duration>,
+    size_limit: Option<usize>,
+}
+
+impl FileReader {
+    /// Creates a new reader.
+    pub fn new() -> Result<FileReader> {
+        Ok(FileReader {
+            ready_state: Rc::new(Cell::new(ReadyState::Empty)),
+            progress: None,
+            timeout: None,
+            size_limit: None,
+        })
+    }
+
+    /// Calls `callback` with the fraction of each read done, like
+    /// [`FileReader::on_progress`](../struct.FileReader.html#method.on_progress).
+    ///
+    /// As reads are done at once, `callback` is only ever called with `1.0`,
+    /// once per successful read.
+    pub fn on_progress<F>(&mut self, callback: F)
+    where
+        F: FnMut(f64) + 'static,
+    {
+        self.progress = Some(Rc::new(RefCell::new(Box::new(callback))));
+    }
+
+    /// Sets how long a read may take, like
+    /// [`FileReader::set_timeout`](../struct.FileReader.html#method.set_timeout).
+    ///
+    /// Reads are done at once, so this never fails one.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Fails reads of blobs larger than `max_bytes` with
+    /// `FileReadError::TooLarge`, like
+    /// [`FileReader::set_size_limit`](../struct.FileReader.html#method.set_size_limit).
+    pub fn set_size_limit(&mut self, max_bytes: usize) {
+        self.size_limit = Some(max_bytes);
+    }
+
+    /// Aborts the read in progress, of which there never is one.
+    pub fn abort(&self) {}
+
+    /// The state of the reader: `Empty` until it has read a blob, and `Done`
+    /// after.
+    ///
+    /// As with the browser's reader, reads of empty blobs and reads refused
+    /// for the size limit leave the state alone.
+    pub fn ready_state(&self) -> ReadyState {
+        self.ready_state.get()
+    }
+
+    /// Reads `blob` as a UTF-8 string.
+    ///
+    /// As in the browser, a byte-order mark is removed and picks the
+    /// encoding, and invalid sequences are replaced by `U+FFFD`.
+    pub fn read_as_string(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.read(blob, decode_with_bom)
+    }
+
+    /// Reads `blob` as a string in the given `encoding`.
+    ///
+    /// The mock knows UTF-8, UTF-16 and windows-1252 (which the web also
+    /// calls `latin1`, `iso-8859-1` and `ascii`). Other labels decode as
+    /// UTF-8, as unknown ones do in the browser. A byte-order mark overrides
+    /// the label.
+    pub fn read_as_string_with_encoding(
+        &self,
+        blob: &DataBlob,
+        encoding: &str,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let encoding = encoding.trim().to_ascii_lowercase();
+        self.read(blob, move |bytes| match (bytes, encoding.as_str()) {
+            ([0xef, 0xbb, 0xbf, ..], _) | ([0xff, 0xfe, ..], _) | ([0xfe, 0xff, ..], _) => {
+                decode_with_bom(bytes)
+            }
+            (_, "utf-16" | "utf-16le") => decode_utf16(bytes, u16::from_le_bytes),
+            (_, "utf-16be") => decode_utf16(bytes, u16::from_be_bytes),
+            (
+                _,
+                "windows-1252" | "cp1252" | "latin1" | "iso-8859-1" | "l1" | "ascii" | "us-ascii",
+            ) => bytes
+                .iter()
+                .map(|&byte| decode_windows_1252(byte))
+                .collect(),
+            _ => decode_with_bom(bytes),
+        })
+    }
+
+    /// Reads `blob` as a binary string, with one `char` per byte.
+    pub fn read_as_binary_string(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.read(blob, |bytes| {
+            bytes.iter().map(|&byte| char::from(byte)).collect()
+        })
+    }
+
+    /// Reads `blob` as raw bytes.
+    pub fn read_as_array_buffer(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        self.read(blob, <[u8]>::to_vec)
+    }
+
+    /// Reads `blob` as a base64-encoded `data:` URL.
+    ///
+    /// Blobs without a type are encoded as `application/octet-stream`.
+    pub fn read_as_data_url(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let mime = match blob.raw_type() {
+            mime if mime.is_empty() => "application/octet-stream".to_string(),
+            mime => mime,
+        };
+        self.read(blob, move |bytes| {
+            format!("data:{};base64,{}", mime, encode_base64(bytes))
+        })
+    }
+
+    /// Settles a read in the same order as the browser-backed reader: empty
+    /// blobs first, without touching the reader, then the size limit.
+    fn read<T>(
+        &self,
+        blob: &DataBlob,
+        extract: impl FnOnce(&[u8]) -> T,
+    ) -> impl Future<Output = Result<T, FileReadError>> {
+        let too_large = self
+            .size_limit
+            .is_some_and(|limit| blob.size() > limit as u64);
+        let result = if blob.is_empty() {
+            Ok(extract(&[]))
+        } else if too_large {
+            Err(FileReadError::TooLarge)
+        } else {
+            self.ready_state.set(ReadyState::Done);
+            Ok(extract(blob.contents()))
+        };
+        if let (Ok(_), Some(progress)) = (&result, &self.progress) {
+            (progress.borrow_mut())(1.0);
+        }
+
+        future::ready(result)
+    }
+}
+
+type ProgressCallback = Box<dyn FnMut(f64)>;
+
+impl fmt::Debug for FileReader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileReader")
+            .field("ready_state", &self.ready_state())
+            .field("on_progress", &self.progress.is_some())
+            .field("timeout", &self.timeout)
+            .field("size_limit", &self.size_limit)
+            .finish()
+    }
+}
+
+impl ReadFile<DataBlob> for FileReader {
+    fn read_as_string(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_string(self, blob)
+    }
+
+    fn read_as_string_with_encoding(
+        &self,
+        blob: &DataBlob,
+        encoding: &str,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_string_with_encoding(self, blob, encoding)
+    }
+
+    fn read_as_binary_string(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_binary_string(self, blob)
+    }
+
+    fn read_as_array_buffer(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        FileReader::read_as_array_buffer(self, blob)
+    }
+
+    fn read_as_data_url(
+        &self,
+        blob: &DataBlob,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_data_url(self, blob)
+    }
+
+    fn abort(&self) {
+        FileReader::abort(self)
+    }
+}
+
+/// Decodes a windows-1252 byte, which is Latin-1 apart from `0x80..=0x9f`.
+fn decode_windows_1252(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}',
+        '\u{8f}', '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}',
+        '\u{2014}', '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}',
+        '\u{178}',
+    ];
+
+    match byte {
+        0x80..=0x9f => HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
@@ -0,0 +1,139 @@
+use crate::RawBlob;
+use std::ops::Deref;
+use wasm_bindgen::UnwrapThrowExt;
+
+/// RAII type for an [object URL](https://developer.mozilla.org/en-US/docs/Web/API/URL/createObjectURL)
+/// pointing at a blob.
+///
+/// Object URLs keep their blob alive until they are revoked, so forgetting to
+/// revoke them leaks memory. When the `ObjectUrl` is dropped, the URL is
+/// revoked automatically.
+///
+/// The `ObjectUrl` dereferences to the URL itself, so it can be used anywhere
+/// a `&str` is expected:
+///
+/// ```no_run
+/// use gloo_file::{DataBlob, ObjectUrl};
+///
+/// let blob = DataBlob::new("...").unwrap();
+/// let url = ObjectUrl::new(&blob);
+///
+/// // Use it as the `src` of an `<img>` or `<video>`, for example.
+/// let src: &str = &url;
+/// ```
+#[must_use = "the object URL is revoked as soon as it is dropped"]
+#[derive(Debug)]
+pub struct ObjectUrl {
+    url: String,
+}
+
+impl ObjectUrl {
+    /// Creates a new object URL pointing at `blob`.
+    pub fn new(blob: &impl RawBlob) -> ObjectUrl {
+        // This only fails for arguments which aren't blobs or media sources.
+        let url = web_sys::Url::create_object_url_with_blob(blob.raw()).unwrap_throw();
+        #[cfg(feature = "debug-leak-tracking")]
+        crate::debug::object_url_created();
+
+        ObjectUrl { url }
+    }
+}
+
+impl Deref for ObjectUrl {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for ObjectUrl {
+    fn drop(&mut self) {
+        // Revoking can't fail for a URL created by `create_object_url`.
+        let _ = web_sys::Url::revoke_object_url(&self.url);
+        #[cfg(feature = "debug-leak-tracking")]
+        crate::debug::object_url_revoked();
+    }
+}
+
+/// A set of [`ObjectUrl`](struct.ObjectUrl.html)s which are revoked
+/// together, such as the URLs of every image in a gallery.
+///
+/// Every URL in the set is revoked when the set is dropped, or earlier with
+/// [`revoke_all`](#method.revoke_all).
+///
+/// ```no_run
+/// use gloo_file::{DataBlob, ObjectUrlSet};
+///
+/// # fn example(images: &[DataBlob], elements: &[web_sys::HtmlImageElement]) {
+/// let mut urls = ObjectUrlSet::new();
+/// for (image, element) in images.iter().zip(elements) {
+///     element.set_src(urls.insert(image));
+/// }
+/// // When the gallery is closed, dropping `urls` revokes all of them.
+/// # }
+/// ```
+#[must_use = "the object URLs are revoked as soon as the set is dropped"]
+#[derive(Debug, Default)]
+pub struct ObjectUrlSet {
+    urls: Vec<ObjectUrl>,
+}
+
+impl ObjectUrlSet {
+    /// Creates an empty set.
+    pub fn new() -> ObjectUrlSet {
+        ObjectUrlSet::default()
+    }
+
+    /// Creates a new object URL pointing at `blob`, adds it to the set, and
+    /// returns it.
+    pub fn insert(&mut self, blob: &impl RawBlob) -> &str {
+        self.push(ObjectUrl::new(blob));
+        self.urls.last().unwrap_throw()
+    }
+
+    /// Adds an existing object URL to the set, which takes over revoking it.
+    pub fn push(&mut self, url: ObjectUrl) {
+        self.urls.push(url);
+    }
+
+    /// Returns the URL at `index`, in the order they were added, or `None`
+    /// if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.urls.get(index).map(|url| &**url)
+    }
+
+    /// Iterates over the URLs in the set, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.urls.iter().map(|url| &**url)
+    }
+
+    /// The number of URLs in the set.
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    /// Returns `true` if the set contains no URLs.
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    /// Revokes every URL in the set, leaving it empty and ready for reuse.
+    pub fn revoke_all(&mut self) {
+        self.urls.clear();
+    }
+}
+
+impl Extend<ObjectUrl> for ObjectUrlSet {
+    fn extend<I: IntoIterator<Item = ObjectUrl>>(&mut self, urls: I) {
+        self.urls.extend(urls);
+    }
+}
+
+impl std::iter::FromIterator<ObjectUrl> for ObjectUrlSet {
+    fn from_iter<I: IntoIterator<Item = ObjectUrl>>(urls: I) -> ObjectUrlSet {
+        ObjectUrlSet {
+            urls: urls.into_iter().collect(),
+        }
+    }
+}
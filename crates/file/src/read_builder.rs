@@ -0,0 +1,297 @@
+use crate::{Blob, DataBlob, Error, FileReadError, FileReader, RawBlob, Result};
+use futures::channel::oneshot;
+use gloo_timers::callback::Timeout;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::time::Duration;
+use wasm_bindgen::{JsValue, UnwrapThrowExt};
+
+/// Configures a single read, for reads which need more than the defaults.
+///
+/// Obtained through [`FileReader::builder`](struct.FileReader.html#method.builder).
+///
+/// ```no_run
+/// use gloo_file::{DataBlob, FileReader};
+/// use std::time::Duration;
+///
+/// let blob = DataBlob::new("...").unwrap();
+/// let contents = FileReader::builder()
+///     .encoding("windows-1252")
+///     .max_bytes(10 * 1024 * 1024)
+///     .timeout(Duration::from_secs(30))
+///     .retries(2)
+///     .on_progress(|fraction| {
+///         // Update a progress bar...
+///     })
+///     .read_string(&blob);
+/// ```
+pub struct FileReadBuilder {
+    encoding: Option<String>,
+    progress: Option<Box<dyn FnMut(f64)>>,
+    max_bytes: Option<usize>,
+    timeout: Option<Duration>,
+    signal: Option<web_sys::AbortSignal>,
+    retries: u32,
+    hooks: Hooks,
+}
+
+/// How long to wait before retrying a read which failed with
+/// `FileReadError::NotReadable`, in milliseconds.
+const RETRY_DELAY: u32 = 100;
+
+impl FileReadBuilder {
+    pub(crate) fn new() -> FileReadBuilder {
+        FileReadBuilder {
+            encoding: None,
+            progress: None,
+            max_bytes: None,
+            timeout: None,
+            signal: None,
+            retries: 0,
+            hooks: Hooks::default(),
+        }
+    }
+
+    /// Decodes strings read with [`read_string`](#method.read_string) from
+    /// `encoding` instead of UTF-8. See
+    /// [`FileReader::read_as_string_with_encoding`](struct.FileReader.html#method.read_as_string_with_encoding).
+    pub fn encoding(mut self, encoding: &str) -> FileReadBuilder {
+        self.encoding = Some(encoding.to_string());
+        self
+    }
+
+    /// Reports progress to `callback`. See
+    /// [`FileReader::on_progress`](struct.FileReader.html#method.on_progress).
+    pub fn on_progress<F>(mut self, callback: F) -> FileReadBuilder
+    where
+        F: FnMut(f64) + 'static,
+    {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Refuses to read blobs larger than `max_bytes`. See
+    /// [`FileReader::set_size_limit`](struct.FileReader.html#method.set_size_limit).
+    pub fn max_bytes(mut self, max_bytes: usize) -> FileReadBuilder {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Aborts the read if it takes longer than `timeout`. See
+    /// [`FileReader::set_timeout`](struct.FileReader.html#method.set_timeout).
+    pub fn timeout(mut self, timeout: Duration) -> FileReadBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Aborts the read when `signal` is aborted. See
+    /// [`FileReader::set_signal`](struct.FileReader.html#method.set_signal).
+    pub fn signal(mut self, signal: &web_sys::AbortSignal) -> FileReadBuilder {
+        self.signal = Some(signal.clone());
+        self
+    }
+
+    /// Retries the read up to `retries` times, after a short delay, while it
+    /// fails with `FileReadError::NotReadable`.
+    ///
+    /// Some browsers, notably Safari on iOS, occasionally fail the first read
+    /// of a freshly selected file but succeed when it is read again. Other
+    /// errors are never retried, and a timeout applies to each attempt
+    /// separately. The lifecycle hooks are called once for the whole read,
+    /// not for each attempt.
+    pub fn retries(mut self, retries: u32) -> FileReadBuilder {
+        self.retries = retries;
+        self
+    }
+
+    /// Calls `hook` once the read has started.
+    pub fn on_start<F>(mut self, hook: F) -> FileReadBuilder
+    where
+        F: FnOnce() + 'static,
+    {
+        self.hooks.start = Some(Box::new(hook));
+        self
+    }
+
+    /// Calls `hook` once the read has loaded successfully.
+    pub fn on_load<F>(mut self, hook: F) -> FileReadBuilder
+    where
+        F: FnOnce() + 'static,
+    {
+        self.hooks.load = Some(Box::new(hook));
+        self
+    }
+
+    /// Calls `hook` with the error the read failed with, if it fails.
+    pub fn on_error<F>(mut self, hook: F) -> FileReadBuilder
+    where
+        F: FnOnce(&FileReadError) + 'static,
+    {
+        self.hooks.error = Some(Box::new(hook));
+        self
+    }
+
+    /// Reads `blob` as a string.
+    pub fn read_string(
+        mut self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let hooks = mem::take(&mut self.hooks);
+        let encoding = self.encoding.take();
+        let retries = self.retries;
+        hooks.around(retrying(
+            self.reader(),
+            blob,
+            retries,
+            move |reader, blob| {
+                let encoding = encoding.clone();
+                reader.start_read(
+                    blob,
+                    move |reader, blob| match &encoding {
+                        Some(encoding) => reader.read_as_text_with_label(blob, encoding),
+                        None => reader.read_as_text(blob),
+                    },
+                    |_| JsValue::from_str(""),
+                    |result| result.as_string().unwrap_throw(),
+                )
+            },
+        ))
+    }
+
+    /// Reads `blob` as raw bytes.
+    pub fn read_bytes(
+        mut self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        let hooks = mem::take(&mut self.hooks);
+        let retries = self.retries;
+        hooks.around(retrying(self.reader(), blob, retries, |reader, blob| {
+            reader.read_as_array_buffer(blob)
+        }))
+    }
+
+    /// Reads `blob` as a `data:` URL.
+    pub fn read_data_url(
+        mut self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        let hooks = mem::take(&mut self.hooks);
+        let retries = self.retries;
+        hooks.around(retrying(self.reader(), blob, retries, |reader, blob| {
+            reader.read_as_data_url(blob)
+        }))
+    }
+
+    fn reader(self) -> Result<FileReader> {
+        let mut reader = FileReader::new()?;
+        if let Some(progress) = self.progress {
+            reader.on_progress(progress);
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            reader.set_size_limit(max_bytes);
+        }
+        if let Some(timeout) = self.timeout {
+            reader.set_timeout(timeout);
+        }
+        if let Some(signal) = &self.signal {
+            reader.set_signal(signal);
+        }
+        Ok(reader)
+    }
+}
+
+impl fmt::Debug for FileReadBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileReadBuilder")
+            .field("encoding", &self.encoding)
+            .field("on_progress", &self.progress.is_some())
+            .field("max_bytes", &self.max_bytes)
+            .field("timeout", &self.timeout)
+            .field("signal", &self.signal)
+            .field("retries", &self.retries)
+            .field("on_start", &self.hooks.start.is_some())
+            .field("on_load", &self.hooks.load.is_some())
+            .field("on_error", &self.hooks.error.is_some())
+            .finish()
+    }
+}
+
+/// Starts `read` of `blob` with `reader`, and starts it again on the same
+/// reader, up to `retries` more times, while it fails with
+/// `FileReadError::NotReadable`.
+fn retrying<T, F, R>(
+    reader: Result<FileReader>,
+    blob: &impl RawBlob,
+    retries: u32,
+    read: F,
+) -> impl Future<Output = Result<T, FileReadError>>
+where
+    F: Fn(&FileReader, &DataBlob) -> R,
+    R: Future<Output = Result<T, FileReadError>>,
+{
+    // Hold the blob itself, so that the returned future doesn't borrow it.
+    let blob = DataBlob::from_raw(blob.raw().clone());
+    let first = reader.map(|reader| {
+        let attempt = read(&reader, &blob);
+        (reader, attempt)
+    });
+
+    async move {
+        let (reader, mut attempt) = first.map_err(Error::into_read_error)?;
+        let mut remaining = retries;
+        loop {
+            match attempt.await {
+                Err(FileReadError::NotReadable) if remaining > 0 => {
+                    remaining -= 1;
+                    sleep(RETRY_DELAY).await;
+                    attempt = read(&reader, &blob);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Resolves after `millis` milliseconds.
+async fn sleep(millis: u32) {
+    let (sender, receiver) = oneshot::channel();
+    let _timeout = Timeout::new(millis, move || {
+        let _ = sender.send(());
+    });
+    let _ = receiver.await;
+}
+
+/// The lifecycle hooks of a read configured by a `FileReadBuilder`.
+#[derive(Default)]
+struct Hooks {
+    start: Option<Box<dyn FnOnce()>>,
+    load: Option<Box<dyn FnOnce()>>,
+    error: Option<ErrorHook>,
+}
+
+type ErrorHook = Box<dyn FnOnce(&FileReadError)>;
+
+impl Hooks {
+    /// Calls the start hook for the just-started `read`, and the load or
+    /// error hook once it finishes.
+    fn around<T>(
+        self,
+        read: impl Future<Output = Result<T, FileReadError>>,
+    ) -> impl Future<Output = Result<T, FileReadError>> {
+        if let Some(start) = self.start {
+            start();
+        }
+        let (load, error) = (self.load, self.error);
+
+        async move {
+            let result = read.await;
+            match (&result, load, error) {
+                (Ok(_), Some(load), _) => load(),
+                (Err(read_error), _, Some(error)) => error(read_error),
+                _ => {}
+            }
+            result
+        }
+    }
+}
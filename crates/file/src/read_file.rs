@@ -0,0 +1,87 @@
+use crate::{Blob, FileReadError, FileReader, RawBlob};
+use std::future::Future;
+
+/// The reads a [`FileReader`](struct.FileReader.html) can do on blobs of
+/// type `B`.
+///
+/// Code which is generic over `impl ReadFile<B>` can be tested with a fake
+/// reader in place of the browser's, such as the `mock` feature's
+/// [`mock::FileReader`](mock/struct.FileReader.html). `FileReader` is the
+/// real implementation, for every browser-backed blob, and each method
+/// behaves as its inherent counterpart.
+///
+/// ```no_run
+/// use gloo_file::{Blob, FileReadError, ReadFile};
+///
+/// async fn word_count<B: Blob>(
+///     reader: &impl ReadFile<B>,
+///     blob: &B,
+/// ) -> Result<usize, FileReadError> {
+///     Ok(reader.read_as_string(blob).await?.split_whitespace().count())
+/// }
+/// ```
+pub trait ReadFile<B: Blob> {
+    /// Reads `blob` as a UTF-8 string.
+    fn read_as_string(&self, blob: &B) -> impl Future<Output = Result<String, FileReadError>>;
+
+    /// Reads `blob` as a string in the given `encoding`.
+    fn read_as_string_with_encoding(
+        &self,
+        blob: &B,
+        encoding: &str,
+    ) -> impl Future<Output = Result<String, FileReadError>>;
+
+    /// Reads `blob` as a "binary string", where each `char` holds one byte.
+    fn read_as_binary_string(
+        &self,
+        blob: &B,
+    ) -> impl Future<Output = Result<String, FileReadError>>;
+
+    /// Reads `blob` as raw bytes.
+    fn read_as_array_buffer(
+        &self,
+        blob: &B,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>>;
+
+    /// Reads `blob` as a `data:` URL.
+    fn read_as_data_url(&self, blob: &B) -> impl Future<Output = Result<String, FileReadError>>;
+
+    /// Aborts the read in progress, if there is one.
+    fn abort(&self);
+}
+
+impl<B: Blob + RawBlob> ReadFile<B> for FileReader {
+    fn read_as_string(&self, blob: &B) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_string(self, blob)
+    }
+
+    fn read_as_string_with_encoding(
+        &self,
+        blob: &B,
+        encoding: &str,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_string_with_encoding(self, blob, encoding)
+    }
+
+    fn read_as_binary_string(
+        &self,
+        blob: &B,
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_binary_string(self, blob)
+    }
+
+    fn read_as_array_buffer(
+        &self,
+        blob: &B,
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        FileReader::read_as_array_buffer(self, blob)
+    }
+
+    fn read_as_data_url(&self, blob: &B) -> impl Future<Output = Result<String, FileReadError>> {
+        FileReader::read_as_data_url(self, blob)
+    }
+
+    fn abort(&self) {
+        FileReader::abort(self)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Blob, Error, File, FileReadError, RawBlob, Result};
+use std::io::{self, Read, Seek, SeekFrom};
+use wasm_bindgen::JsValue;
+
+/// How many bytes [`FileReadSeek`](struct.FileReadSeek.html) reads from the
+/// file at once.
+const CHUNK_SIZE: u64 = 64 * 1024;
+
+/// A synchronous `Read + Seek` view of a [`File`](struct.File.html), for
+/// parsers which expect `std::io` rather than futures.
+///
+/// The file is read lazily, a 64 KB chunk at a time around the current
+/// position, with the last chunk kept to serve nearby reads, so it never
+/// needs to be held in memory all at once. This reads with the browser's
+/// `FileReaderSync`, which blocks and so only exists in workers; elsewhere,
+/// read the file with [`File::into_cursor`](struct.File.html#method.into_cursor)
+/// instead.
+///
+/// ```no_run
+/// use gloo_file::FileReadSeek;
+/// use std::io::{Read, Seek, SeekFrom};
+///
+/// # fn example(file: &gloo_file::File) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut reader = FileReadSeek::new(file)?;
+/// let mut trailer = [0; 22];
+/// reader.seek(SeekFrom::End(-22))?;
+/// reader.read_exact(&mut trailer)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileReadSeek {
+    blob: web_sys::Blob,
+    size: u64,
+    position: u64,
+    reader: web_sys::FileReaderSync,
+    chunk_start: u64,
+    chunk: Vec<u8>,
+}
+
+impl FileReadSeek {
+    /// Creates a reader positioned at the start of `file`.
+    ///
+    /// This fails with `Error::Unsupported` outside of workers.
+    pub fn new(file: &File) -> Result<FileReadSeek> {
+        let supported =
+            js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("FileReaderSync"))?;
+        if !supported {
+            return Err(Error::Unsupported("FileReaderSync"));
+        }
+
+        Ok(FileReadSeek {
+            blob: file.raw().clone(),
+            size: file.size(),
+            position: 0,
+            reader: web_sys::FileReaderSync::new()?,
+            chunk_start: 0,
+            chunk: Vec::new(),
+        })
+    }
+
+    /// Reads the chunk starting at the current position, at least `len`
+    /// bytes long where the file allows.
+    fn fill_chunk(&mut self, len: usize) -> io::Result<()> {
+        let start = self.position;
+        let end = self.size.min(start + CHUNK_SIZE.max(len as u64));
+        let slice = self
+            .blob
+            .slice_with_f64_and_f64(start as f64, end as f64)
+            .map_err(to_io_error)?;
+        let buffer = self
+            .reader
+            .read_as_array_buffer(&slice)
+            .map_err(to_io_error)?;
+
+        self.chunk = js_sys::Uint8Array::new(&buffer).to_vec();
+        self.chunk_start = start;
+        Ok(())
+    }
+}
+
+fn to_io_error(error: JsValue) -> io::Error {
+    // `FileReadError` can hold a `JsValue`, which isn't `Send`, so only its
+    // message is kept.
+    io::Error::other(FileReadError::from_js(error).to_string())
+}
+
+impl Read for FileReadSeek {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.size || buf.is_empty() {
+            return Ok(0);
+        }
+        let chunk_end = self.chunk_start + self.chunk.len() as u64;
+        if self.position < self.chunk_start || self.position >= chunk_end {
+            self.fill_chunk(buf.len())?;
+        }
+
+        let offset = (self.position - self.chunk_start) as usize;
+        // The chunk is empty if the file shrank since it was selected.
+        let available = self.chunk.get(offset..).unwrap_or_default();
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+impl Seek for FileReadSeek {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match position {
+            SeekFrom::Start(offset) => {
+                self.position = offset;
+                return Ok(offset);
+            }
+            SeekFrom::End(offset) => (self.size, offset),
+            SeekFrom::Current(offset) => (self.position, offset),
+        };
+        match base.checked_add_signed(offset) {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+/// How long a read took, from
+/// [`File::bytes_with_stats`](struct.File.html#method.bytes_with_stats).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadStats {
+    /// How many bytes were read.
+    pub bytes: u64,
+    /// How long the read took, in milliseconds.
+    pub elapsed_ms: f64,
+    /// The bytes read per second, or `0.0` if the read was too quick for the
+    /// browser's clock to measure.
+    pub throughput_bytes_per_sec: f64,
+}
+
+impl ReadStats {
+    pub(crate) fn new(bytes: u64, elapsed_ms: f64) -> ReadStats {
+        // Browsers coarsen the clock, so a small read can take no time at all.
+        let throughput_bytes_per_sec = if elapsed_ms > 0.0 {
+            bytes as f64 / (elapsed_ms / 1000.0)
+        } else {
+            0.0
+        };
+
+        ReadStats {
+            bytes,
+            elapsed_ms,
+            throughput_bytes_per_sec,
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{Blob, DataBlob, Error, FileReadError, FileReader, RawBlob};
+use futures::channel::oneshot;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+use wasm_bindgen::UnwrapThrowExt;
+
+/// A fixed number of [`FileReader`](struct.FileReader.html)s shared between
+/// many reads, so that a large batch holds a bounded number of readers.
+///
+/// Each read borrows an idle reader for as long as it runs. Readers are
+/// created as they are first needed, up to the pool's size, and a read which
+/// finds all of them busy waits its turn. Cloning a `FileReaderPool` gives
+/// another handle to the same pool.
+///
+/// ```no_run
+/// use gloo_file::{FileList, FileReaderPool};
+///
+/// # fn spawn_local(_: impl std::future::Future<Output = ()> + 'static) {}
+/// # async fn example(files: FileList) {
+/// let pool = FileReaderPool::new(4);
+/// for file in &files {
+///     let read = pool.read_as_array_buffer(&file);
+///     spawn_local(async move {
+///         let contents = read.await;
+///         // ...
+///     });
+/// }
+///
+/// // Before tearing down, let every read finish.
+/// pool.join().await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FileReaderPool {
+    inner: Rc<Pool>,
+}
+
+struct Pool {
+    size: usize,
+    /// How many readers exist, whether idle or reading.
+    created: Cell<usize>,
+    idle: RefCell<Vec<FileReader>>,
+    /// The reads waiting for a reader, oldest first.
+    waiting: RefCell<VecDeque<oneshot::Sender<FileReader>>>,
+    /// How many reads have been started and not yet finished or dropped.
+    pending: Cell<usize>,
+    joining: RefCell<Vec<oneshot::Sender<()>>>,
+}
+
+impl FileReaderPool {
+    /// Creates a pool of at most `size` readers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is `0`.
+    pub fn new(size: usize) -> FileReaderPool {
+        assert!(size != 0, "`size` must be at least 1");
+        FileReaderPool {
+            inner: Rc::new(Pool {
+                size,
+                created: Cell::new(0),
+                idle: RefCell::new(Vec::new()),
+                waiting: RefCell::new(VecDeque::new()),
+                pending: Cell::new(0),
+                joining: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Reads `blob` as a UTF-8 string with one of the pool's readers.
+    pub fn read_as_string(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.run(blob, |reader, blob| reader.read_as_string(blob))
+    }
+
+    /// Reads `blob` as raw bytes with one of the pool's readers.
+    pub fn read_as_array_buffer(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<Vec<u8>, FileReadError>> {
+        self.run(blob, |reader, blob| reader.read_as_array_buffer(blob))
+    }
+
+    /// Reads `blob` as a `data:` URL with one of the pool's readers.
+    pub fn read_as_data_url(
+        &self,
+        blob: &(impl Blob + RawBlob),
+    ) -> impl Future<Output = Result<String, FileReadError>> {
+        self.run(blob, |reader, blob| reader.read_as_data_url(blob))
+    }
+
+    /// Resolves once no reads are running or waiting for a reader, so that
+    /// the pool can be torn down without cutting any short.
+    ///
+    /// This counts every read future the pool has returned which hasn't
+    /// finished or been dropped, whether or not it has been polled, so make
+    /// sure those futures are being awaited. Reads started while waiting
+    /// delay it further.
+    pub fn join(&self) -> impl Future<Output = ()> {
+        let (sender, receiver) = oneshot::channel();
+        if self.inner.pending.get() == 0 {
+            let _ = sender.send(());
+        } else {
+            self.inner.joining.borrow_mut().push(sender);
+        }
+
+        async move {
+            let _ = receiver.await;
+        }
+    }
+
+    fn run<T, F, R>(
+        &self,
+        blob: &impl RawBlob,
+        read: F,
+    ) -> impl Future<Output = Result<T, FileReadError>>
+    where
+        F: FnOnce(&FileReader, &DataBlob) -> R,
+        R: Future<Output = Result<T, FileReadError>>,
+    {
+        // Hold the blob itself, so that the returned future doesn't borrow it.
+        let blob = DataBlob::from_raw(blob.raw().clone());
+        let pending = Pending::new(self.inner.clone());
+
+        async move {
+            let lease = Lease::acquire(pending.0.clone()).await?;
+            // Declared after the lease, so that if this future is dropped
+            // the read is aborted before its reader goes back to the pool.
+            let read = read(lease.reader(), &blob);
+            read.await
+        }
+    }
+}
+
+impl fmt::Debug for FileReaderPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FileReaderPool")
+            .field("size", &self.inner.size)
+            .field("idle", &self.inner.idle.borrow().len())
+            .field("pending", &self.inner.pending.get())
+            .finish()
+    }
+}
+
+impl Pool {
+    /// Hands `reader` to the oldest read still waiting, or keeps it idle.
+    fn release(&self, mut reader: FileReader) {
+        loop {
+            let waiter = self.waiting.borrow_mut().pop_front();
+            match waiter {
+                // A waiter whose read was dropped gives the reader back.
+                Some(waiter) => match waiter.send(reader) {
+                    Ok(()) => return,
+                    Err(unsent) => reader = unsent,
+                },
+                None => {
+                    self.idle.borrow_mut().push(reader);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Counts a read as pending from when it is created until it is dropped.
+struct Pending(Rc<Pool>);
+
+impl Pending {
+    fn new(pool: Rc<Pool>) -> Pending {
+        pool.pending.set(pool.pending.get() + 1);
+        Pending(pool)
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        let pending = self.0.pending.get() - 1;
+        self.0.pending.set(pending);
+        if pending == 0 {
+            for joiner in self.0.joining.borrow_mut().drain(..) {
+                let _ = joiner.send(());
+            }
+        }
+    }
+}
+
+/// A read waiting for a reader, which passes one on if it is dropped after
+/// the reader was sent but before it was received.
+struct Waiter {
+    pool: Rc<Pool>,
+    receiver: oneshot::Receiver<FileReader>,
+}
+
+impl Drop for Waiter {
+    fn drop(&mut self) {
+        self.receiver.close();
+        if let Ok(Some(reader)) = self.receiver.try_recv() {
+            self.pool.release(reader);
+        }
+    }
+}
+
+/// A reader borrowed from the pool, which goes back when this is dropped.
+struct Lease {
+    pool: Rc<Pool>,
+    reader: Option<FileReader>,
+}
+
+impl Lease {
+    async fn acquire(pool: Rc<Pool>) -> Result<Lease, FileReadError> {
+        let idle = pool.idle.borrow_mut().pop();
+        let reader = match idle {
+            Some(reader) => reader,
+            None if pool.created.get() < pool.size => {
+                let reader = FileReader::new().map_err(Error::into_read_error)?;
+                pool.created.set(pool.created.get() + 1);
+                reader
+            }
+            None => {
+                let (sender, receiver) = oneshot::channel();
+                pool.waiting.borrow_mut().push_back(sender);
+                let mut waiter = Waiter {
+                    pool: pool.clone(),
+                    receiver,
+                };
+                // The pool only drops waiters by handing them a reader.
+                (&mut waiter.receiver)
+                    .await
+                    .map_err(|_| FileReadError::Aborted)?
+            }
+        };
+
+        Ok(Lease {
+            pool,
+            reader: Some(reader),
+        })
+    }
+
+    fn reader(&self) -> &FileReader {
+        self.reader.as_ref().unwrap_throw()
+    }
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            self.pool.release(reader);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! A micro-benchmark of the allocations made while reading text, run in a
+//! browser like the Web tests.
+//!
+//! This is its own test crate because it replaces the global allocator.
+
+#![cfg(target_arch = "wasm32")]
+
+use gloo_file::{DataBlob, FileReader};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size.saturating_sub(layout.size()), Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(Debug, Clone, Copy)]
+struct Counts {
+    allocations: usize,
+    reallocations: usize,
+    bytes: usize,
+}
+
+fn counts() -> Counts {
+    Counts {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        reallocations: REALLOCATIONS.load(Ordering::Relaxed),
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+    }
+}
+
+fn since(before: Counts) -> Counts {
+    let after = counts();
+    Counts {
+        allocations: after.allocations - before.allocations,
+        reallocations: after.reallocations - before.reallocations,
+        bytes: after.bytes - before.bytes,
+    }
+}
+
+#[wasm_bindgen_test]
+async fn read_as_string_capacity_allocations() {
+    // 4 MB of text which isn't ASCII, so its UTF-8 length can't be guessed
+    // from the JavaScript string's.
+    let text = "caf\u{e9} ".repeat(4 * 1024 * 1024 / 6);
+    let blob = DataBlob::new(&text).unwrap();
+    let reader = FileReader::new().unwrap();
+
+    let before = counts();
+    let plain = reader.read_as_string(&blob).await.unwrap();
+    let plain_counts = since(before);
+
+    let before = counts();
+    let reserved = reader.read_as_string_capacity(&blob).await.unwrap();
+    let reserved_counts = since(before);
+
+    console_log!("read_as_string:          {:?}", plain_counts);
+    console_log!("read_as_string_capacity: {:?}", reserved_counts);
+
+    assert_eq!(plain, text);
+    assert_eq!(reserved, text);
+    assert!(reserved_counts.reallocations < plain_counts.reallocations);
+    assert!(reserved_counts.bytes < plain_counts.bytes);
+    // The string's buffer is allocated once, at exactly the blob's size.
+    assert!(reserved_counts.bytes < text.len() + 4096);
+}
@@ -0,0 +1,505 @@
+//! Tests for the parts of the crate that don't need a browser, runnable with
+//! a plain `cargo test`.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use gloo_file::{
+    humanize_bytes, sanitize_filename, AcceptList, ByteSize, ChunkedReadProgress, Error,
+    FileReadError, MimeCategory, MimeType,
+};
+
+fn parse(raw: &str) -> MimeType {
+    raw.parse().unwrap()
+}
+
+#[test]
+fn mime_type_known() {
+    assert_eq!(parse("application/json").as_str(), "application/json");
+    assert_eq!(parse("image/svg+xml").as_str(), "image/svg+xml");
+    assert_eq!(parse("").as_str(), "");
+}
+
+#[test]
+fn mime_type_ignores_case_and_parameters() {
+    assert_eq!(parse("Text/Plain").as_str(), "text/plain");
+    assert_eq!(parse("text/plain;charset=utf-8").as_str(), "text/plain");
+    assert_eq!(parse(" text/html ; charset=utf-8").as_str(), "text/html");
+}
+
+#[test]
+fn mime_type_aliases() {
+    assert_eq!(parse("application/javascript").as_str(), "text/javascript");
+    assert_eq!(parse("text/xml").as_str(), "application/xml");
+    assert_eq!(parse("audio/x-wav").as_str(), "audio/wav");
+}
+
+#[test]
+fn mime_type_other_keeps_raw_string() {
+    let mime = parse("application/x-custom; version=2");
+    match &mime {
+        MimeType::Other(raw) => assert_eq!(raw, "application/x-custom; version=2"),
+        _ => panic!("expected `Other`"),
+    }
+    assert_eq!(mime.to_string(), "application/x-custom; version=2");
+}
+
+#[test]
+fn mime_type_eq() {
+    assert_eq!(parse("image/png"), MimeType::ImagePng);
+    assert_ne!(parse("image/png"), MimeType::ImageJpeg);
+    assert_eq!(parse("a/b"), MimeType::Other("a/b".to_string()));
+    assert_ne!(parse("a/b"), MimeType::Other("A/B".to_string()));
+}
+
+#[test]
+fn mime_type_from_magic_bytes() {
+    let sniff = MimeType::from_magic_bytes;
+
+    assert_eq!(
+        sniff(b"\x89PNG\r\n\x1a\n\0\0\0\x0d"),
+        Some(MimeType::ImagePng)
+    );
+    assert_eq!(sniff(b"\xff\xd8\xff\xe0"), Some(MimeType::ImageJpeg));
+    assert_eq!(sniff(b"GIF89a"), Some(MimeType::ImageGif));
+    assert_eq!(sniff(b"RIFF\0\0\0\0WEBP"), Some(MimeType::ImageWebp));
+    assert_eq!(sniff(b"RIFF\0\0\0\0WAVE"), Some(MimeType::AudioWav));
+    assert_eq!(sniff(b"%PDF-1.4"), Some(MimeType::ApplicationPdf));
+    assert_eq!(sniff(b"PK\x03\x04"), Some(MimeType::ApplicationZip));
+    assert_eq!(sniff(b"\0\0\0\x18ftypmp42"), Some(MimeType::VideoMp4));
+    assert_eq!(sniff(b"\0\0\0\x20ftypisom"), Some(MimeType::VideoMp4));
+    assert_eq!(sniff(b"\0\0\0\x1cftypavc1"), Some(MimeType::VideoMp4));
+
+    assert_eq!(sniff(b"RIFF\0\0\0\0AVI "), None);
+    assert_eq!(sniff(b"hello world"), None);
+    assert_eq!(sniff(b""), None);
+}
+
+#[test]
+fn mime_type_from_magic_bytes_iso_brands() {
+    let sniff = MimeType::from_magic_bytes;
+    let other = |raw: &str| Some(MimeType::Other(raw.to_string()));
+
+    // iPhone photos are HEIC, whose major brand is `heic` or `mif1`.
+    assert_eq!(sniff(b"\0\0\0\x18ftypheic\0\0\0\0"), other("image/heic"));
+    assert_eq!(sniff(b"\0\0\0\x18ftypheix"), other("image/heic"));
+    assert_eq!(sniff(b"\0\0\0\x18ftypmif1"), other("image/heif"));
+    assert_eq!(sniff(b"\0\0\0\x1cftypavif"), other("image/avif"));
+    assert_eq!(sniff(b"\0\0\0\x20ftypM4A "), other("audio/mp4"));
+    assert_eq!(
+        sniff(b"\0\0\0\x18ftypheic").unwrap().category(),
+        MimeCategory::Image
+    );
+
+    // Unknown brands, and a box cut off before its brand, aren't guessed.
+    assert_eq!(sniff(b"\0\0\0\x14ftypqt  "), None);
+    assert_eq!(sniff(b"\0\0\0\x18ftyp"), None);
+}
+
+#[test]
+fn humanize() {
+    assert_eq!(humanize_bytes(0), "0 B");
+    assert_eq!(humanize_bytes(1023), "1023 B");
+    assert_eq!(humanize_bytes(1024), "1.0 KB");
+    assert_eq!(humanize_bytes(1536), "1.5 KB");
+    assert_eq!(humanize_bytes(5 * 1024 * 1024), "5.0 MB");
+    assert_eq!(humanize_bytes(3 << 30), "3.0 GB");
+    assert_eq!(ByteSize(6 << 40).to_string(), "6.0 TB");
+    assert_eq!(ByteSize(u64::MAX).to_string(), "16384.0 PB");
+}
+
+#[test]
+fn mime_type_matches_pattern() {
+    assert!(MimeType::ImagePng.matches_pattern("*"));
+    assert!(MimeType::ImagePng.matches_pattern("*/*"));
+    assert!(MimeType::ImagePng.matches_pattern("image/*"));
+    assert!(MimeType::ImagePng.matches_pattern("IMAGE/PNG"));
+    assert!(!MimeType::ImagePng.matches_pattern("video/*"));
+    assert!(!MimeType::ImagePng.matches_pattern("image/jpeg"));
+    assert!(!MimeType::ImagePng.matches_pattern(".png"));
+
+    assert!(MimeType::TextCsv.matches_pattern(".csv, text/csv"));
+    assert!(parse("text/csv; charset=utf-8").matches_pattern("text/csv"));
+    assert!(parse("application/x-custom").matches_pattern("application/*"));
+
+    assert!(MimeType::Unknown.matches_pattern("*"));
+    assert!(!MimeType::Unknown.matches_pattern("image/*"));
+    assert!(!MimeType::Unknown.matches_pattern(""));
+}
+
+#[test]
+fn mime_type_category() {
+    assert_eq!(MimeType::ImageSvg.category(), MimeCategory::Image);
+    assert_eq!(MimeType::AudioWav.category(), MimeCategory::Audio);
+    assert_eq!(MimeType::VideoWebm.category(), MimeCategory::Video);
+    assert_eq!(MimeType::TextCsv.category(), MimeCategory::Text);
+    assert_eq!(
+        MimeType::ApplicationZip.category(),
+        MimeCategory::Application
+    );
+    assert_eq!(parse("Image/AVIF").category(), MimeCategory::Image);
+    assert_eq!(parse("font/woff2").category(), MimeCategory::Other);
+    assert_eq!(parse("nonsense").category(), MimeCategory::Other);
+    assert_eq!(MimeType::Unknown.category(), MimeCategory::Other);
+}
+
+#[test]
+fn accept_list() {
+    assert_eq!(AcceptList::new().to_string(), "");
+    assert!(AcceptList::new().is_empty());
+
+    let mut accept = AcceptList::new();
+    accept
+        .mime(MimeType::TextCsv)
+        .extension(".CSV")
+        .extension("csv")
+        .mime(parse("Text/CSV"))
+        .category(MimeCategory::Video)
+        .category(MimeCategory::Other)
+        .mime(MimeType::Unknown)
+        .extension("");
+    assert_eq!(accept.to_string(), "text/csv,.csv,video/*");
+
+    let accept = accept.to_string();
+    assert!(MimeType::TextCsv.matches_pattern(&accept));
+    assert!(MimeType::VideoMp4.matches_pattern(&accept));
+    assert!(!MimeType::TextPlain.matches_pattern(&accept));
+}
+
+#[test]
+fn error_display_and_source() {
+    use std::error::Error as _;
+
+    let unsupported = Error::Unsupported("the document");
+    assert_eq!(
+        unsupported.to_string(),
+        "this environment does not support the document"
+    );
+    assert!(unsupported.source().is_none());
+
+    let read = Error::from(FileReadError::Aborted);
+    assert_eq!(read.to_string(), "the read was aborted");
+    assert!(read.source().is_some());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn file_metadata_round_trip() {
+    let metadata = gloo_file::FileMetadata {
+        name: "report.csv".to_string(),
+        size: 1234,
+        mime_type: "text/csv".to_string(),
+        last_modified: 1_600_000_000_000.0,
+        relative_path: Some("reports/report.csv".to_string()),
+    };
+    let json = serde_json::to_string(&metadata).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"report.csv","size":1234,"mime_type":"text/csv","last_modified":1600000000000.0,"relative_path":"reports/report.csv"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<gloo_file::FileMetadata>(&json).unwrap(),
+        metadata
+    );
+}
+
+#[test]
+fn mime_type_preferred_extension() {
+    assert_eq!(
+        MimeType::ApplicationJson.preferred_extension(),
+        Some("json")
+    );
+    assert_eq!(MimeType::ImagePng.preferred_extension(), Some("png"));
+    assert_eq!(MimeType::Unknown.preferred_extension(), None);
+
+    let other = |raw: &str| raw.parse::<MimeType>().unwrap().preferred_extension();
+    assert_eq!(other("image/AVIF; q=0.9"), Some("avif"));
+    assert_eq!(other("application/x-unheard-of"), None);
+}
+
+#[test]
+fn mime_type_from_extension() {
+    assert_eq!(MimeType::from_extension("csv"), Some(MimeType::TextCsv));
+    assert_eq!(MimeType::from_extension("JPG"), Some(MimeType::ImageJpeg));
+    assert_eq!(
+        MimeType::from_extension("md"),
+        Some(MimeType::Other("text/markdown".to_string()))
+    );
+    assert_eq!(MimeType::from_extension(""), None);
+
+    // Every preferred extension maps back to its type.
+    for mime in [
+        MimeType::ApplicationJson,
+        MimeType::ImageSvg,
+        MimeType::AudioMpeg,
+    ] {
+        let extension = mime.preferred_extension().unwrap();
+        assert_eq!(MimeType::from_extension(extension), Some(mime));
+    }
+}
+
+#[test]
+fn chunked_read_progress() {
+    let mut progress = ChunkedReadProgress::new(10, 4);
+    assert_eq!(progress.chunk_count(), 3);
+    assert_eq!(progress.fraction(), 0.0);
+
+    // The short last chunk completes first.
+    assert!(progress.complete(2));
+    assert!(!progress.complete(2));
+    assert_eq!(progress.completed_bytes(), 2);
+    assert!(progress.is_complete(2));
+    assert!(!progress.is_complete(3));
+
+    assert!(progress.complete(1));
+    assert_eq!(progress.remaining().collect::<Vec<_>>(), [0]);
+    assert!(!progress.is_done());
+
+    progress.complete(0);
+    assert!(progress.is_done());
+    assert_eq!(progress.fraction(), 1.0);
+
+    let empty = ChunkedReadProgress::new(0, 4);
+    assert_eq!(empty.chunk_count(), 0);
+    assert!(empty.is_done());
+    assert_eq!(empty.fraction(), 1.0);
+}
+
+#[test]
+fn sanitize_filename_strips_unsafe_characters() {
+    assert_eq!(sanitize_filename("report.csv"), "report.csv");
+    assert_eq!(sanitize_filename("a/b\\c.txt"), "abc.txt");
+    assert_eq!(sanitize_filename("what?<is>:this*|\"\u{7}"), "whatisthis");
+    assert_eq!(sanitize_filename("  notes.txt. . "), "notes.txt");
+    assert_eq!(sanitize_filename(".gitignore"), "gitignore");
+    assert_eq!(sanitize_filename("../../.ssh/config"), "sshconfig");
+    assert_eq!(sanitize_filename(" . .hidden.txt"), "hidden.txt");
+    assert_eq!(sanitize_filename("../.."), "download");
+    assert_eq!(sanitize_filename(""), "download");
+}
+
+#[test]
+fn sanitize_filename_reserved_names() {
+    assert_eq!(sanitize_filename("CON"), "_CON");
+    assert_eq!(sanitize_filename("nul.txt"), "_nul.txt");
+    assert_eq!(sanitize_filename("com1.tar.gz"), "_com1.tar.gz");
+    assert_eq!(sanitize_filename("lpt9 .log"), "_lpt9 .log");
+    assert_eq!(sanitize_filename("console.log"), "console.log");
+    assert_eq!(sanitize_filename("com10"), "com10");
+    // Windows ignores trailing dots and spaces, so these are reserved too.
+    assert_eq!(sanitize_filename("CON."), "_CON");
+    assert_eq!(sanitize_filename("aux . "), "_aux");
+    assert_eq!(sanitize_filename(".prn.txt"), "_prn.txt");
+    assert_eq!(sanitize_filename("c/o/n"), "_con");
+}
+
+#[test]
+fn sanitize_filename_truncates_keeping_extension() {
+    let long = format!("{}.pdf", "a".repeat(300));
+    let sanitized = sanitize_filename(&long);
+    assert_eq!(sanitized.len(), 255);
+    assert!(sanitized.ends_with("aaa.pdf"));
+
+    // Multi-byte characters aren't split.
+    let sanitized = sanitize_filename(&format!("{}.txt", "é".repeat(200)));
+    assert!(sanitized.len() <= 255);
+    assert!(sanitized.ends_with("é.txt"));
+
+    // Only the last extension is kept.
+    let sanitized = sanitize_filename(&format!("{}.tar.gz", "a".repeat(300)));
+    assert_eq!(sanitized.len(), 255);
+    assert!(sanitized.ends_with("aaa.gz"));
+
+    // Names at the limit are left alone, and the reserved-name prefix counts
+    // towards it.
+    let at_limit = format!("{}.txt", "a".repeat(251));
+    assert_eq!(sanitize_filename(&at_limit), at_limit);
+    let sanitized = sanitize_filename(&format!("nul.{}.txt", "a".repeat(247)));
+    assert_eq!(sanitized.len(), 255);
+    assert!(sanitized.starts_with("_nul.") && sanitized.ends_with("a.txt"));
+
+    // A cut which leaves a reserved name still gets the prefix.
+    let spaced = |stem: &str| format!("{}{}x.txt", stem, " ".repeat(300));
+    assert_eq!(sanitize_filename(&spaced("CON")), "_CON.txt");
+    assert_eq!(sanitize_filename(&spaced("aux")), "_aux.txt");
+
+    // Trailing dots left by the cut are removed before the extension.
+    let sanitized = sanitize_filename(&format!("{}{}.pdf", "a".repeat(250), ".".repeat(10)));
+    assert_eq!(sanitized, format!("{}.pdf", "a".repeat(250)));
+
+    // The longest extension kept is 31 bytes, with its dot.
+    let extension = format!(".{}", "e".repeat(30));
+    let sanitized = sanitize_filename(&format!("{}{}", "a".repeat(300), extension));
+    assert!(sanitized.ends_with(&extension));
+    let extension = format!(".{}", "e".repeat(31));
+    let sanitized = sanitize_filename(&format!("{}{}", "a".repeat(300), extension));
+    assert_eq!(sanitized, "a".repeat(255));
+
+    // An implausibly long extension is cut along with the rest.
+    let sanitized = sanitize_filename(&format!("a.{}", "b".repeat(300)));
+    assert_eq!(sanitized.len(), 255);
+    assert!(sanitized.starts_with("a.b"));
+}
+
+#[cfg(feature = "mock")]
+mod mock {
+    use futures::executor::block_on;
+    use gloo_file::mock::{DataBlob, FileReader};
+    use gloo_file::{Blob, FileReadError, MimeType, ReadFile, ReadyState};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn blob_type() {
+        let blob = DataBlob::new_with_type("{}", "Application/JSON").unwrap();
+        assert_eq!(blob.raw_type(), "application/json");
+        assert_eq!(blob.mime_type(), MimeType::ApplicationJson);
+
+        let untyped = DataBlob::new_with_type("", "text/plain\u{e9}").unwrap();
+        assert_eq!(untyped.raw_type(), "");
+    }
+
+    #[test]
+    fn slice() {
+        let blob = DataBlob::new_with_type("hello world", "text/plain").unwrap();
+
+        let world = blob.slice(6, 100);
+        assert_eq!(world.len(), 5);
+        assert_eq!(world.raw_type(), "");
+        assert_eq!(block_on(world.text()).unwrap(), "world");
+        assert_eq!(block_on(world.slice(1, 3).bytes()).unwrap(), b"or");
+
+        assert!(blob.slice(8, 2).is_empty());
+        assert!(blob.slice(20, 30).is_empty());
+        assert!(blob.slice(0, 5).same_object(&blob.slice(0, 5)));
+        assert!(!blob.slice(0, 5).same_object(&blob.slice(0, 4)));
+    }
+
+    #[test]
+    fn concatenate() {
+        let a = DataBlob::new_with_type("a", "text/plain").unwrap();
+        let b = DataBlob::new_with_type("b", "text/plain").unwrap();
+        let c = DataBlob::new("c").unwrap();
+
+        let ab = &a + &b;
+        assert_eq!(ab.raw_type(), "text/plain");
+        let abc = ab + c;
+        assert_eq!(abc.raw_type(), "");
+        assert_eq!(block_on(abc.text()).unwrap(), "abc");
+    }
+
+    #[test]
+    fn reads() {
+        let reader = FileReader::new().unwrap();
+        let blob = DataBlob::from_bytes(b"\xef\xbb\xbfh\xe9\xff").unwrap();
+
+        assert_eq!(
+            block_on(reader.read_as_string(&blob)).unwrap(),
+            "h\u{fffd}\u{fffd}"
+        );
+        assert_eq!(
+            block_on(reader.read_as_binary_string(&blob)).unwrap(),
+            "\u{ef}\u{bb}\u{bf}h\u{e9}\u{ff}"
+        );
+        assert_eq!(
+            block_on(reader.read_as_array_buffer(&blob)).unwrap(),
+            b"\xef\xbb\xbfh\xe9\xff"
+        );
+
+        let utf16 = DataBlob::from_bytes(b"\xff\xfeh\x00i\x00").unwrap();
+        assert_eq!(block_on(reader.read_as_string(&utf16)).unwrap(), "hi");
+    }
+
+    #[test]
+    fn data_url() {
+        let reader = FileReader::new().unwrap();
+
+        let png = DataBlob::from_bytes_with_type(b"\x89PNG", "image/png").unwrap();
+        assert_eq!(
+            block_on(reader.read_as_data_url(&png)).unwrap(),
+            "data:image/png;base64,iVBORw=="
+        );
+        let untyped = DataBlob::new("hi").unwrap();
+        assert_eq!(
+            block_on(reader.read_as_data_url(&untyped)).unwrap(),
+            "data:application/octet-stream;base64,aGk="
+        );
+        assert_eq!(
+            block_on(reader.read_as_data_url(&DataBlob::new("").unwrap())).unwrap(),
+            "data:application/octet-stream;base64,"
+        );
+    }
+
+    #[test]
+    fn size_limit() {
+        let mut reader = FileReader::new().unwrap();
+        reader.set_size_limit(4);
+
+        let blob = DataBlob::new("hello").unwrap();
+        assert!(matches!(
+            block_on(reader.read_as_string(&blob)),
+            Err(FileReadError::TooLarge)
+        ));
+        assert_eq!(
+            block_on(reader.read_as_string(&blob.slice(0, 4))).unwrap(),
+            "hell"
+        );
+    }
+
+    #[test]
+    fn encodings() {
+        let reader = FileReader::new().unwrap();
+
+        let blob = DataBlob::from_bytes(b"h\0i\0").unwrap();
+        assert_eq!(
+            block_on(reader.read_as_string_with_encoding(&blob, "UTF-16LE")).unwrap(),
+            "hi"
+        );
+        let blob = DataBlob::from_bytes(b"\x80 caf\xe9").unwrap();
+        assert_eq!(
+            block_on(reader.read_as_string_with_encoding(&blob, "latin1")).unwrap(),
+            "\u{20ac} caf\u{e9}"
+        );
+        let blob = DataBlob::from_bytes(b"\xef\xbb\xbfcaf\xc3\xa9").unwrap();
+        assert_eq!(
+            block_on(reader.read_as_string_with_encoding(&blob, "windows-1252")).unwrap(),
+            "caf\u{e9}"
+        );
+        assert_eq!(
+            block_on(reader.read_as_string_with_encoding(&blob, "no-such-encoding")).unwrap(),
+            "caf\u{e9}"
+        );
+    }
+
+    #[test]
+    fn ready_state_and_progress() {
+        let mut reader = FileReader::new().unwrap();
+        let reported = Rc::new(Cell::new(None));
+        reader.on_progress({
+            let reported = reported.clone();
+            move |fraction| reported.set(Some(fraction))
+        });
+        assert_eq!(reader.ready_state(), ReadyState::Empty);
+
+        block_on(reader.read_as_string(&DataBlob::new("").unwrap())).unwrap();
+        assert_eq!(reader.ready_state(), ReadyState::Empty);
+        assert_eq!(reported.take(), Some(1.0));
+
+        block_on(reader.read_as_string(&DataBlob::new("hi").unwrap())).unwrap();
+        assert_eq!(reader.ready_state(), ReadyState::Done);
+        assert_eq!(reported.take(), Some(1.0));
+    }
+
+    #[test]
+    fn read_file() {
+        async fn shout<B: Blob>(reader: &impl ReadFile<B>, blob: &B) -> String {
+            let text = reader.read_as_string(blob).await.unwrap();
+            let bytes = reader.read_as_array_buffer(blob).await.unwrap();
+            assert_eq!(bytes, text.as_bytes());
+            text.to_uppercase()
+        }
+
+        let reader = FileReader::new().unwrap();
+        let blob = DataBlob::new("hello").unwrap();
+        assert_eq!(block_on(shout(&reader, &blob)), "HELLO");
+    }
+}
@@ -0,0 +1,2095 @@
+//! Test suite for the Web and headless browsers.
+
+#![cfg(target_arch = "wasm32")]
+
+use futures::prelude::*;
+use gloo_file::{
+    download, Blob, BlobBuilder, BlobOptions, DataBlob, File, FileConstraints, FileList,
+    FileReadError, FileReader, FileReaderPool, FileValidationError, MimeCategory, MimeType,
+    ObjectUrl, ObjectUrlSet, ProgressEvent, RawBlob, ReadKind, ReadResult, ReadyState,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen_test::*;
+use web_sys::{window, HtmlInputElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn raw_file(contents: &str, name: &str, mime: &str) -> web_sys::File {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = web_sys::FilePropertyBag::new();
+    options.set_type(mime);
+    web_sys::File::new_with_str_sequence_and_options(&parts, name, &options).unwrap_throw()
+}
+
+/// Builds a `FileList` the same way a user would, by selecting files in an
+/// `<input type="file">`.
+fn file_list(files: &[web_sys::File]) -> FileList {
+    let transfer = web_sys::DataTransfer::new().unwrap_throw();
+    for file in files {
+        transfer.items().add_with_file(file).unwrap_throw();
+    }
+
+    let input: HtmlInputElement = window()
+        .unwrap_throw()
+        .document()
+        .unwrap_throw()
+        .create_element("input")
+        .unwrap_throw()
+        .unchecked_into();
+    input.set_type("file");
+    input.set_files(transfer.files().as_ref());
+
+    FileList::new(&input).unwrap_throw()
+}
+
+fn file(contents: &str, name: &str, mime: &str) -> File {
+    file_list(&[raw_file(contents, name, mime)])
+        .get(0)
+        .unwrap_throw()
+}
+
+#[wasm_bindgen_test]
+async fn read_as_string() {
+    let blob = DataBlob::new("hello world").unwrap();
+
+    let contents = FileReader::new()
+        .unwrap()
+        .read_as_string(&blob)
+        .await
+        .unwrap();
+    assert_eq!(contents, "hello world");
+}
+
+#[wasm_bindgen_test]
+async fn read_as_string_capacity() {
+    let reader = FileReader::new().unwrap();
+    let inputs: &[&[u8]] = &[
+        b"",
+        b"hello world",
+        "caf\u{e9} \u{1f600}".as_bytes(),
+        b"\xef\xbb\xbfwith a BOM",
+        b"\xff\xfeh\x00i\x00",
+        b"bad \xff bytes \xe2\x82",
+    ];
+
+    for &input in inputs {
+        let blob = DataBlob::from_bytes(input).unwrap();
+        let expected = reader.read_as_string(&blob).await.unwrap();
+        assert_eq!(
+            reader.read_as_string_capacity(&blob).await.unwrap(),
+            expected
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+async fn read_as_string_with_encoding() {
+    // "café" in windows-1252.
+    let blob = DataBlob::from_bytes(&[0x63, 0x61, 0x66, 0xe9]).unwrap();
+
+    let contents = FileReader::new()
+        .unwrap()
+        .read_as_string_with_encoding(&blob, "windows-1252")
+        .await
+        .unwrap();
+
+    assert_eq!(contents, "café");
+}
+
+#[wasm_bindgen_test]
+async fn read_as_array_buffer() {
+    let blob = DataBlob::new("hello world").unwrap();
+
+    let bytes = FileReader::new()
+        .unwrap()
+        .read_as_array_buffer(&blob)
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"hello world");
+}
+
+#[wasm_bindgen_test]
+async fn read_empty_as_array_buffer() {
+    let blob = DataBlob::new("").unwrap();
+
+    let bytes = FileReader::new()
+        .unwrap()
+        .read_as_array_buffer(&blob)
+        .await
+        .unwrap();
+    assert!(bytes.is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn read_as_data_url() {
+    let blob = DataBlob::new("hello world").unwrap();
+
+    let url = FileReader::new()
+        .unwrap()
+        .read_as_data_url(&blob)
+        .await
+        .unwrap();
+    assert_eq!(url, "data:application/octet-stream;base64,aGVsbG8gd29ybGQ=");
+}
+
+#[wasm_bindgen_test]
+fn mime_type_round_trip() {
+    for raw in &[
+        "application/json",
+        "image/svg+xml",
+        "application/x-custom",
+        "",
+    ] {
+        let mime: MimeType = raw.parse().unwrap();
+        assert_eq!(mime.to_string(), *raw);
+    }
+
+    let mime: MimeType = "text/plain;charset=utf-8".parse().unwrap();
+    assert_eq!(mime.to_string(), "text/plain");
+}
+
+#[wasm_bindgen_test]
+async fn slice() {
+    let blob = DataBlob::new("hello world").unwrap();
+
+    assert_eq!(blob.slice(6, 100).size(), 5);
+    assert_eq!(blob.slice(8, 2).size(), 0);
+
+    let contents = FileReader::new()
+        .unwrap()
+        .read_as_string(&blob.slice(0, 5))
+        .await
+        .unwrap();
+    assert_eq!(contents, "hello");
+}
+
+#[wasm_bindgen_test]
+async fn abort() {
+    let blob = DataBlob::new("hello world").unwrap();
+    let reader = FileReader::new().unwrap();
+
+    let read = reader.read_as_string(&blob);
+    reader.abort();
+
+    match read.await {
+        Err(FileReadError::Aborted) => {}
+        other => panic!("expected an abort, got {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn on_progress() {
+    // Large enough that the browser fires `progress` before the read ends.
+    let contents = vec![0; 64 * 1024 * 1024];
+    let blob = File::new(&contents, "zeros.bin", "").unwrap();
+    let fractions = Rc::new(RefCell::new(Vec::new()));
+
+    let mut reader = FileReader::new().unwrap();
+    reader.on_progress({
+        let fractions = fractions.clone();
+        move |fraction| fractions.borrow_mut().push(fraction)
+    });
+    let bytes = reader.read_as_array_buffer(&blob).await.unwrap();
+    assert_eq!(bytes.len(), contents.len());
+
+    let fractions = fractions.borrow();
+    let (last, partial) = fractions.split_last().unwrap();
+    assert_eq!(*last, 1.0);
+    assert!(partial
+        .iter()
+        .any(|&fraction| fraction > 0.0 && fraction < 1.0));
+    assert!(fractions.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[wasm_bindgen_test]
+async fn data_blob_from_raw() {
+    let parts = js_sys::Array::of1(&"hello world".into());
+    let raw = web_sys::Blob::new_with_str_sequence(&parts).unwrap();
+    let blob = DataBlob::from_raw(raw);
+
+    let contents = FileReader::new()
+        .unwrap()
+        .read_as_string(&blob)
+        .await
+        .unwrap();
+    assert_eq!(contents, "hello world");
+}
+
+#[wasm_bindgen_test]
+async fn data_blob_from_bytes() {
+    let blob = DataBlob::from_bytes_with_type(&[0x89, b'P', b'N', b'G'], "image/png").unwrap();
+    assert_eq!(blob.mime_type().to_string(), "image/png");
+
+    let bytes = FileReader::new()
+        .unwrap()
+        .read_as_array_buffer(&blob)
+        .await
+        .unwrap();
+    assert_eq!(bytes, [0x89, b'P', b'N', b'G']);
+}
+
+#[wasm_bindgen_test]
+fn data_blob_with_type() {
+    let blob = DataBlob::new_with_type("{}", "application/json").unwrap();
+
+    assert_eq!(blob.mime_type(), MimeType::ApplicationJson);
+}
+
+#[wasm_bindgen_test]
+async fn reuse_reader() {
+    let reader = FileReader::new().unwrap();
+
+    let first = DataBlob::new("first").unwrap();
+    assert_eq!(reader.read_as_string(&first).await.unwrap(), "first");
+
+    let second = DataBlob::new("second").unwrap();
+    assert_eq!(
+        reader.read_as_array_buffer(&second).await.unwrap(),
+        b"second"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn busy_reader() {
+    let reader = FileReader::new().unwrap();
+    let first = DataBlob::new("first").unwrap();
+    let second = DataBlob::new("second").unwrap();
+
+    let first = reader.read_as_string(&first);
+    let (second, progress) = reader.read_as_array_buffer_with_progress(&second);
+    match second.await {
+        Err(FileReadError::JsError(error)) => {
+            let exception: web_sys::DomException = error.dyn_into().unwrap();
+            assert_eq!(exception.name(), "InvalidStateError");
+        }
+        other => panic!("expected the busy read to fail, got {:?}", other),
+    }
+    assert_eq!(progress.collect::<Vec<_>>().await, []);
+
+    // The failed read left the first one's handlers in place.
+    assert_eq!(first.await.unwrap(), "first");
+}
+
+#[wasm_bindgen_test]
+fn object_url() {
+    let blob = DataBlob::new("hello world").unwrap();
+    let url = ObjectUrl::new(&blob);
+
+    assert!(url.starts_with("blob:"));
+    drop(url);
+}
+
+#[wasm_bindgen_test]
+fn object_url_set() {
+    let first = DataBlob::new("first").unwrap();
+    let second = DataBlob::new("second").unwrap();
+
+    let mut urls = ObjectUrlSet::new();
+    let url = urls.insert(&first).to_string();
+    urls.push(ObjectUrl::new(&second));
+    assert_eq!(urls.len(), 2);
+    assert_eq!(urls.get(0), Some(&*url));
+    assert!(urls.iter().all(|url| url.starts_with("blob:")));
+    assert_ne!(urls.get(0), urls.get(1));
+
+    urls.revoke_all();
+    assert!(urls.is_empty());
+    assert!(urls.get(0).is_none());
+}
+
+#[wasm_bindgen_test]
+async fn stream_chunks() {
+    let file = file("hello world", "hello.txt", "text/plain");
+
+    let chunks: Vec<_> = file.stream_chunks(4).map(Result::unwrap).collect().await;
+    assert_eq!(chunks, [&b"hell"[..], b"o wo", b"rld"]);
+}
+
+#[wasm_bindgen_test]
+async fn file_text_and_bytes() {
+    let file = file("hello world", "hello.txt", "text/plain");
+
+    assert_eq!(file.text().await.unwrap(), "hello world");
+    assert_eq!(file.bytes().await.unwrap(), b"hello world");
+}
+
+async fn read_generic(blob: &(impl Blob + RawBlob)) -> (String, Vec<u8>) {
+    (
+        blob.text().await.unwrap(),
+        blob.array_buffer().await.unwrap(),
+    )
+}
+
+#[wasm_bindgen_test]
+async fn blob_text_and_array_buffer() {
+    let blob = DataBlob::new("hello").unwrap();
+    assert_eq!(
+        read_generic(&blob).await,
+        ("hello".to_string(), b"hello".to_vec())
+    );
+
+    let file = file("world", "world.txt", "text/plain");
+    assert_eq!(
+        read_generic(&file).await,
+        ("world".to_string(), b"world".to_vec())
+    );
+}
+
+#[wasm_bindgen_test]
+fn relative_path_of_single_file() {
+    let file = file("hello world", "hello.txt", "text/plain");
+
+    assert_eq!(file.name(), "hello.txt");
+    assert_eq!(file.relative_path(), None);
+}
+
+#[wasm_bindgen_test]
+async fn blob_builder() {
+    let existing = DataBlob::new(" world").unwrap();
+    let blob = BlobBuilder::new()
+        .append_str("hello")
+        .append_blob(&existing)
+        .append_bytes(b"!")
+        .mime_type("text/plain")
+        .build()
+        .unwrap();
+
+    assert_eq!(blob.mime_type().to_string(), "text/plain");
+    assert_eq!(blob.text().await.unwrap(), "hello world!");
+}
+
+#[wasm_bindgen_test]
+fn raw_type() {
+    let blob = DataBlob::new_with_type("hello", "text/plain;charset=utf-8").unwrap();
+    assert_eq!(blob.raw_type(), "text/plain;charset=utf-8");
+    assert_eq!(blob.mime_type().as_str(), "text/plain");
+
+    let file = file("hello", "hello.txt", "text/plain;charset=utf-8");
+    assert_eq!(file.raw_type(), "text/plain;charset=utf-8");
+}
+
+#[wasm_bindgen_test]
+async fn new_file() {
+    let file = File::new(b"a,b\n1,2\n", "report.csv", "text/csv").unwrap();
+
+    assert_eq!(file.name(), "report.csv");
+    assert_eq!(file.mime_type().as_str(), "text/csv");
+    assert_eq!(file.size(), 8);
+    assert_eq!(file.text().await.unwrap(), "a,b\n1,2\n");
+}
+
+#[wasm_bindgen_test]
+fn debug() {
+    let file = file("hello", "hello.txt", "text/plain");
+    assert_eq!(
+        format!("{:?}", file),
+        r#"File { name: "hello.txt", size: 5, mime_type: TextPlain }"#
+    );
+
+    let files = file_list(&[raw_file("hello", "hello.txt", "text/plain")]);
+    assert_eq!(format!("{:?}", files), "FileList { len: 1 }");
+}
+
+#[wasm_bindgen_test]
+async fn read_within_timeout() {
+    let blob = DataBlob::new("hello world").unwrap();
+    let mut reader = FileReader::new().unwrap();
+    reader.set_timeout(std::time::Duration::from_secs(10));
+
+    assert_eq!(reader.read_as_string(&blob).await.unwrap(), "hello world");
+    assert_eq!(reader.read_as_string(&blob).await.unwrap(), "hello world");
+}
+
+#[wasm_bindgen_test]
+async fn size_limit() {
+    let mut reader = FileReader::new().unwrap();
+    reader.set_size_limit(5);
+
+    let small = DataBlob::new("hello").unwrap();
+    assert_eq!(reader.read_as_string(&small).await.unwrap(), "hello");
+
+    let large = DataBlob::new("hello world").unwrap();
+    match reader.read_as_string(&large).await {
+        Err(FileReadError::TooLarge) => {}
+        other => panic!("expected `TooLarge`, got {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test]
+fn named_iter() {
+    let files = file_list(&[
+        raw_file("a", "a.txt", "text/plain"),
+        raw_file("b", "b.txt", "text/plain"),
+    ]);
+
+    let names: Vec<_> = files.named_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["a.txt", "b.txt"]);
+}
+
+#[wasm_bindgen_test]
+async fn read_as_binary_string() {
+    let blob = DataBlob::from_bytes(&[0x00, 0x41, 0xff]).unwrap();
+
+    let contents = FileReader::new()
+        .unwrap()
+        .read_as_binary_string(&blob)
+        .await
+        .unwrap();
+
+    assert_eq!(contents, "\u{0}A\u{ff}");
+}
+
+#[wasm_bindgen_test]
+fn from_drag_event() {
+    let transfer = web_sys::DataTransfer::new().unwrap_throw();
+    transfer
+        .items()
+        .add_with_file(&raw_file("hello", "hello.txt", "text/plain"))
+        .unwrap_throw();
+    let init = web_sys::DragEventInit::new();
+    init.set_data_transfer(Some(&transfer));
+    let event = web_sys::DragEvent::new_with_event_init_dict("drop", &init).unwrap_throw();
+
+    let files = FileList::from_drag_event(&event).unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files.get(0).unwrap().name(), "hello.txt");
+
+    let empty = web_sys::DragEvent::new("drop").unwrap_throw();
+    assert!(FileList::from_drag_event(&empty).is_none());
+}
+
+#[wasm_bindgen_test]
+fn from_change_event() {
+    let transfer = web_sys::DataTransfer::new().unwrap_throw();
+    transfer
+        .items()
+        .add_with_file(&raw_file("hello", "hello.txt", "text/plain"))
+        .unwrap_throw();
+    let document = window().unwrap_throw().document().unwrap_throw();
+    let input: HtmlInputElement = document
+        .create_element("input")
+        .unwrap_throw()
+        .unchecked_into();
+    input.set_type("file");
+    input.set_files(transfer.files().as_ref());
+    let event = web_sys::Event::new("change").unwrap_throw();
+    input.dispatch_event(&event).unwrap_throw();
+
+    let files = FileList::from_change_event(&event).unwrap();
+    assert_eq!(files.get(0).unwrap().name(), "hello.txt");
+
+    let text: HtmlInputElement = document
+        .create_element("input")
+        .unwrap_throw()
+        .unchecked_into();
+    let event = web_sys::Event::new("change").unwrap_throw();
+    text.dispatch_event(&event).unwrap_throw();
+    assert!(FileList::from_change_event(&event).is_none());
+}
+
+#[wasm_bindgen_test]
+fn as_raw() {
+    let files = file_list(&[raw_file("hello", "hello.txt", "text/plain")]);
+    assert_eq!(files.as_raw().length(), 1);
+
+    let file = files.get(0).unwrap();
+    assert_eq!(file.as_raw().name(), "hello.txt");
+    assert_eq!(file.into_raw().size(), 5.0);
+    assert_eq!(files.into_raw().length(), 1);
+}
+
+#[cfg(feature = "digest")]
+#[wasm_bindgen_test]
+async fn digest_sha256() {
+    let file = File::new(b"abc", "abc.txt", "text/plain").unwrap();
+
+    let digest = file.digest_sha256().await.unwrap();
+    assert_eq!(
+        digest[..],
+        [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ][..]
+    );
+}
+
+#[cfg(feature = "digest")]
+#[wasm_bindgen_test]
+async fn verify_sha256() {
+    let file = File::new(b"abc", "abc.txt", "text/plain").unwrap();
+    let mut expected = file.digest_sha256().await.unwrap();
+    assert!(file.verify_sha256(&expected).await.unwrap());
+
+    expected[31] ^= 1;
+    assert!(!file.verify_sha256(&expected).await.unwrap());
+}
+
+#[cfg(feature = "image")]
+#[wasm_bindgen_test]
+async fn image_dimensions() {
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="3" height="2"></svg>"#;
+    let image = File::new(svg, "image.svg", "image/svg+xml").unwrap();
+    assert_eq!(image.image_dimensions().await.unwrap(), (3, 2));
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    match text.image_dimensions().await {
+        Err(FileReadError::InvalidImage) => {}
+        other => panic!("expected `InvalidImage`, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "image")]
+#[wasm_bindgen_test]
+async fn decode_bitmap() {
+    // A single transparent pixel.
+    let png = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f,
+        0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0x64,
+        0x60, 0xf8, 0x5f, 0x0f, 0x00, 0x02, 0x87, 0x01, 0x80, 0xeb, 0x47, 0xba, 0x92, 0x00, 0x00,
+        0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+    let image = File::new(&png, "pixel.png", "image/png").unwrap();
+    let bitmap = image.decode_bitmap().await.unwrap();
+    assert_eq!((bitmap.width(), bitmap.height()), (1, 1));
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    match text.decode_bitmap().await {
+        Err(FileReadError::InvalidImage) => {}
+        other => panic!("expected `InvalidImage`, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "image")]
+#[wasm_bindgen_test]
+async fn thumbnail_data_url() {
+    // A 4 by 2 red rectangle.
+    let png = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06, 0x00, 0x00, 0x00, 0x7f,
+        0xa8, 0x7d, 0x63, 0x00, 0x00, 0x00, 0x12, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0xf8,
+        0xcf, 0xc0, 0xf0, 0x1f, 0x19, 0x33, 0xa0, 0x0b, 0x00, 0x00, 0x0f, 0x21, 0x0f, 0xf1, 0xfe,
+        0x45, 0x14, 0x63, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+    let image = File::new(&png, "red.png", "image/png").unwrap();
+    let url = image.thumbnail_data_url(2, "image/png", 1.0).await.unwrap();
+    let base64 = url.strip_prefix("data:image/png;base64,").unwrap();
+
+    let bytes: Vec<u8> = window()
+        .unwrap_throw()
+        .atob(base64)
+        .unwrap()
+        .chars()
+        .map(|byte| byte as u8)
+        .collect();
+    let thumbnail = File::new(&bytes, "thumbnail.png", "image/png").unwrap();
+    let bitmap = thumbnail.decode_bitmap().await.unwrap();
+    assert_eq!((bitmap.width(), bitmap.height()), (2, 1));
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    match text.thumbnail_data_url(100, "image/png", 1.0).await {
+        Err(FileReadError::InvalidImage) => {}
+        other => panic!("expected `InvalidImage`, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "image")]
+#[wasm_bindgen_test]
+async fn image_data() {
+    // A 4 by 2 red rectangle.
+    let png = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06, 0x00, 0x00, 0x00, 0x7f,
+        0xa8, 0x7d, 0x63, 0x00, 0x00, 0x00, 0x12, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0xf8,
+        0xcf, 0xc0, 0xf0, 0x1f, 0x19, 0x33, 0xa0, 0x0b, 0x00, 0x00, 0x0f, 0x21, 0x0f, 0xf1, 0xfe,
+        0x45, 0x14, 0x63, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+    let image = File::new(&png, "red.png", "image/png").unwrap();
+    let pixels = image.image_data().await.unwrap();
+    assert_eq!((pixels.width(), pixels.height()), (4, 2));
+    let data = pixels.data();
+    assert_eq!(data.len(), 4 * 2 * 4);
+    assert!(data.chunks(4).all(|pixel| pixel == &data[..4]));
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    match text.image_data().await {
+        Err(FileReadError::InvalidImage) => {}
+        other => panic!("expected `InvalidImage`, got {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test]
+fn download_cleans_up() {
+    let body = window()
+        .unwrap_throw()
+        .document()
+        .unwrap_throw()
+        .body()
+        .unwrap_throw();
+    let children = body.child_element_count();
+
+    download(&DataBlob::new("hello").unwrap(), "hello.txt").unwrap();
+    assert_eq!(body.child_element_count(), children);
+}
+
+#[wasm_bindgen_test]
+fn owned_iter() {
+    fn names(files: FileList) -> impl Iterator<Item = String> {
+        files.into_iter().map(|file| file.name().to_string())
+    }
+
+    let files = file_list(&[
+        raw_file("a", "a.txt", "text/plain"),
+        raw_file("b", "b.txt", "text/plain"),
+    ]);
+    assert_eq!(names(files).collect::<Vec<_>>(), ["a.txt", "b.txt"]);
+}
+
+#[wasm_bindgen_test]
+async fn ready_state() {
+    let reader = FileReader::new().unwrap();
+    assert_eq!(reader.ready_state(), ReadyState::Empty);
+
+    let contents = reader.read_as_string(&DataBlob::new("hello").unwrap());
+    assert_eq!(reader.ready_state(), ReadyState::Loading);
+
+    contents.await.unwrap();
+    assert_eq!(reader.ready_state(), ReadyState::Done);
+}
+
+#[wasm_bindgen_test]
+async fn read_range() {
+    let file = file("hello world", "hello.txt", "text/plain");
+
+    assert_eq!(file.read_range(0, 5).await.unwrap(), b"hello");
+    assert_eq!(file.read_range(6, 100).await.unwrap(), b"world");
+    assert!(file.read_range(11, 20).await.unwrap().is_empty());
+    assert!(file.read_range(5, 2).await.unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn sniff_mime() {
+    let png = File::new(b"\x89PNG\r\n\x1a\n", "image.txt", "text/plain").unwrap();
+    assert_eq!(png.sniff_mime().await.unwrap(), MimeType::ImagePng);
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    assert_eq!(text.sniff_mime().await.unwrap(), MimeType::TextPlain);
+}
+
+#[wasm_bindgen_test]
+async fn read_with_sniffed_type() {
+    let png = File::new(b"\x89PNG\r\n\x1a\nrest", "image.txt", "text/plain").unwrap();
+    let (bytes, mime) = png.read_with_sniffed_type().await.unwrap();
+    assert_eq!(bytes, b"\x89PNG\r\n\x1a\nrest");
+    assert_eq!(mime, MimeType::ImagePng);
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    let (bytes, mime) = text.read_with_sniffed_type().await.unwrap();
+    assert_eq!(bytes, b"hello");
+    assert_eq!(mime, MimeType::TextPlain);
+}
+
+#[wasm_bindgen_test]
+async fn read_builder() {
+    let blob = DataBlob::from_bytes(&[0x63, 0x61, 0x66, 0xe9]).unwrap();
+
+    let contents = FileReader::builder()
+        .encoding("windows-1252")
+        .timeout(std::time::Duration::from_secs(10))
+        .read_string(&blob)
+        .await
+        .unwrap();
+    assert_eq!(contents, "café");
+
+    match FileReader::builder().max_bytes(2).read_bytes(&blob).await {
+        Err(FileReadError::TooLarge) => {}
+        other => panic!("expected `TooLarge`, got {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn read_all() {
+    let files = file_list(&[
+        raw_file("a", "a.txt", "text/plain"),
+        raw_file("bb", "b.txt", "text/plain"),
+        raw_file("ccc", "c.txt", "text/plain"),
+    ]);
+
+    let text: Vec<_> = files
+        .read_all_text()
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(text, ["a", "bb", "ccc"]);
+
+    let buffered: Vec<_> = files
+        .read_all_bytes_buffered(2)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(buffered, [&b"a"[..], b"bb", b"ccc"]);
+}
+
+#[wasm_bindgen_test]
+fn display() {
+    let file = File::new(&[0; 2048], "report.pdf", "application/pdf").unwrap();
+    assert_eq!(file.to_string(), "report.pdf (2.0 KB, application/pdf)");
+    assert_eq!(file.size_bytes(), 2048);
+
+    let untyped = File::new(b"hello", "hello", "").unwrap();
+    assert_eq!(untyped.to_string(), "hello (5 B)");
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+async fn read_json() {
+    #[derive(serde::Deserialize)]
+    struct Config {
+        name: String,
+    }
+
+    let file = File::new(br#"{"name": "gloo"}"#, "config.json", "application/json").unwrap();
+    let config: Config = file.read_json().await.unwrap();
+    assert_eq!(config.name, "gloo");
+
+    let file = File::new(b"{", "config.json", "application/json").unwrap();
+    match file.read_json::<Config>().await {
+        Err(FileReadError::Json(_)) => {}
+        other => panic!("expected `Json`, got {:?}", other.map(|config| config.name)),
+    }
+}
+
+#[wasm_bindgen_test]
+fn ordering() {
+    let mut files = [
+        File::new(b"bb", "b.txt", "text/plain").unwrap(),
+        File::new(b"a", "a.txt", "text/plain").unwrap(),
+        File::new(b"", "b.txt", "text/plain").unwrap(),
+    ];
+    files.sort();
+
+    let sorted: Vec<_> = files
+        .iter()
+        .map(|file| (file.name(), file.size()))
+        .collect();
+    assert_eq!(sorted, [("a.txt", 1), ("b.txt", 0), ("b.txt", 2)]);
+    assert_eq!(files[0], files[0].clone());
+}
+
+#[wasm_bindgen_test]
+async fn read_into() {
+    let mut buf = b"> ".to_vec();
+
+    let hello = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    assert_eq!(hello.read_into(&mut buf).await.unwrap(), 5);
+    let empty = File::new(b"", "empty.txt", "text/plain").unwrap();
+    assert_eq!(empty.read_into(&mut buf).await.unwrap(), 0);
+
+    assert_eq!(buf, b"> hello");
+}
+
+#[wasm_bindgen_test]
+fn file_list_of_empty_and_non_file_inputs() {
+    let document = window().unwrap_throw().document().unwrap_throw();
+
+    let input: HtmlInputElement = document
+        .create_element("input")
+        .unwrap_throw()
+        .unchecked_into();
+    assert!(FileList::new(&input).is_none());
+
+    input.set_type("file");
+    let files = FileList::new(&input).unwrap();
+    assert!(files.is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn data_blob_with_options() {
+    let options = BlobOptions {
+        mime_type: Some("text/plain".to_string()),
+        endings: Some(gloo_file::Endings::Transparent),
+    };
+    let blob = DataBlob::new_with_options("one\r\ntwo\n", &options).unwrap();
+
+    assert_eq!(blob.mime_type(), MimeType::TextPlain);
+    assert_eq!(blob.text().await.unwrap(), "one\r\ntwo\n");
+}
+
+#[wasm_bindgen_test]
+async fn into_read_stream() {
+    let files = file_list(&[
+        raw_file("a", "a.txt", "text/plain"),
+        raw_file("bb", "b.txt", "text/plain"),
+    ]);
+
+    let mut stream = Box::pin(files.into_read_stream());
+    let (file, bytes) = stream.next().await.unwrap().unwrap();
+    assert_eq!((file.name(), &bytes[..]), ("a.txt", &b"a"[..]));
+    let (file, bytes) = stream.next().await.unwrap().unwrap();
+    assert_eq!((file.name(), &bytes[..]), ("b.txt", &b"bb"[..]));
+    assert!(stream.next().await.is_none());
+}
+
+#[wasm_bindgen_test]
+fn from_clipboard_event() {
+    let transfer = web_sys::DataTransfer::new().unwrap_throw();
+    transfer
+        .items()
+        .add_with_file(&raw_file("image", "image.png", "image/png"))
+        .unwrap_throw();
+    let init = web_sys::ClipboardEventInit::new();
+    init.set_clipboard_data(Some(&transfer));
+    let event = web_sys::ClipboardEvent::new_with_event_init_dict("paste", &init).unwrap_throw();
+
+    let files = FileList::from_clipboard_event(&event).unwrap();
+    assert_eq!(files.get(0).unwrap().name(), "image.png");
+
+    let text = web_sys::DataTransfer::new().unwrap_throw();
+    text.set_data("text/plain", "hello").unwrap_throw();
+    let init = web_sys::ClipboardEventInit::new();
+    init.set_clipboard_data(Some(&text));
+    let event = web_sys::ClipboardEvent::new_with_event_init_dict("paste", &init).unwrap_throw();
+    assert!(FileList::from_clipboard_event(&event).is_none());
+}
+
+#[wasm_bindgen_test]
+async fn read_file_trait() {
+    async fn shout(reader: &impl gloo_file::ReadFile<DataBlob>, blob: &DataBlob) -> String {
+        reader.read_as_string(blob).await.unwrap().to_uppercase()
+    }
+
+    let blob = DataBlob::new("hello").unwrap();
+    assert_eq!(shout(&FileReader::new().unwrap(), &blob).await, "HELLO");
+}
+
+#[wasm_bindgen_test]
+async fn data_blob_add() {
+    let hello = DataBlob::new_with_type("hello ", "text/plain").unwrap();
+    let world = DataBlob::new_with_type("world", "text/plain").unwrap();
+    let joined = hello + &world;
+    assert_eq!(joined.mime_type(), MimeType::TextPlain);
+    assert_eq!(joined.text().await.unwrap(), "hello world");
+
+    let untyped = joined + DataBlob::new("!").unwrap();
+    assert_eq!(untyped.mime_type(), MimeType::Unknown);
+    assert_eq!(untyped.text().await.unwrap(), "hello world!");
+}
+
+#[wasm_bindgen_test]
+async fn last_error() {
+    let reader = FileReader::new().unwrap();
+    reader
+        .read_as_string(&DataBlob::new("hello").unwrap())
+        .await
+        .unwrap();
+    assert!(reader.last_error().is_none());
+}
+
+#[wasm_bindgen_test]
+fn extension() {
+    let extension = |name| File::new(b"", name, "").unwrap().extension();
+
+    assert_eq!(extension("photo.JPG").as_deref(), Some("jpg"));
+    assert_eq!(extension("archive.tar.gz").as_deref(), Some("gz"));
+    assert_eq!(extension(".config.json").as_deref(), Some("json"));
+    assert_eq!(extension(".bashrc"), None);
+    assert_eq!(extension("README"), None);
+    assert_eq!(extension("trailing."), None);
+}
+
+#[cfg(feature = "fs_access")]
+#[wasm_bindgen_test]
+async fn open_file_picker_needs_user_activation() {
+    // Without a click to open it, the picker is either refused or missing.
+    let options = gloo_file::FilePickerOptions::default();
+    assert!(gloo_file::open_file_picker(&options).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn text_lossy() {
+    let file = File::new(b"caf\xe9!", "cafe.txt", "text/plain").unwrap();
+    assert_eq!(file.text_lossy().await.unwrap(), "caf\u{fffd}!");
+}
+
+#[wasm_bindgen_test]
+async fn read_all_with_progress() {
+    let files = file_list(&[
+        raw_file("a", "a.txt", "text/plain"),
+        raw_file("bbb", "b.txt", "text/plain"),
+    ]);
+
+    let reports = Rc::new(RefCell::new(Vec::new()));
+    let contents = files
+        .read_all_with_progress({
+            let reports = reports.clone();
+            move |index, total, fraction| reports.borrow_mut().push((index, total, fraction))
+        })
+        .await;
+
+    assert!(contents.iter().all(Result::is_ok));
+    let reports = reports.borrow();
+    assert!(reports
+        .iter()
+        .all(|&(index, total, _)| index < 2 && total == 2));
+    assert!(reports.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    assert_eq!(reports.last().unwrap().2, 1.0);
+}
+
+#[wasm_bindgen_test]
+async fn data_blob_bytes() {
+    let blob = DataBlob::from_bytes(&[1, 2, 3]).unwrap();
+    assert_eq!(blob.bytes().await.unwrap(), [1, 2, 3]);
+}
+
+#[wasm_bindgen_test]
+fn form_data() {
+    let form = web_sys::FormData::new().unwrap_throw();
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    gloo_file::append_to_form_data(&form, "upload", &file).unwrap();
+    let blob = DataBlob::new("world").unwrap();
+    gloo_file::append_blob_to_form_data(&form, "extra", &blob, "world.txt").unwrap();
+
+    let uploaded: web_sys::File = form.get("upload").unchecked_into();
+    assert_eq!(uploaded.name(), "hello.txt");
+    let extra: web_sys::File = form.get("extra").unchecked_into();
+    assert_eq!(extra.name(), "world.txt");
+}
+
+#[wasm_bindgen_test]
+async fn drop_read_early() {
+    let reader = FileReader::new().unwrap();
+    let blob = DataBlob::new("hello").unwrap();
+
+    let read = reader.read_as_string(&blob);
+    assert_eq!(reader.ready_state(), ReadyState::Loading);
+    drop(read);
+    assert_eq!(reader.ready_state(), ReadyState::Done);
+
+    // Give the aborted read's events a chance to fire, which would throw if
+    // they called freed closures.
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    let _timeout = gloo_timers::callback::Timeout::new(10, move || sender.send(()).unwrap());
+    receiver.await.unwrap();
+
+    assert_eq!(reader.read_as_string(&blob).await.unwrap(), "hello");
+}
+
+#[cfg(feature = "canvas")]
+#[wasm_bindgen_test]
+async fn file_from_canvas() {
+    let canvas: web_sys::HtmlCanvasElement = window()
+        .unwrap_throw()
+        .document()
+        .unwrap_throw()
+        .create_element("canvas")
+        .unwrap_throw()
+        .unchecked_into();
+    canvas.set_width(4);
+    canvas.set_height(3);
+
+    let file = gloo_file::file_from_canvas(&canvas, "image/png", 1.0, "canvas.png")
+        .await
+        .unwrap();
+    assert_eq!(file.name(), "canvas.png");
+    assert_eq!(file.mime_type(), MimeType::ImagePng);
+    assert_eq!(file.sniff_mime().await.unwrap(), MimeType::ImagePng);
+}
+
+#[wasm_bindgen_test]
+async fn read_builder_hooks() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let hook = |name: &'static str| {
+        let events = events.clone();
+        move || events.borrow_mut().push(name)
+    };
+
+    FileReader::builder()
+        .on_start(hook("start"))
+        .on_load(hook("load"))
+        .on_error({
+            let events = events.clone();
+            move |_| events.borrow_mut().push("error")
+        })
+        .read_string(&DataBlob::new("hello").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(*events.borrow(), ["start", "load"]);
+
+    events.borrow_mut().clear();
+    FileReader::builder()
+        .max_bytes(1)
+        .on_start(hook("start"))
+        .on_load(hook("load"))
+        .on_error({
+            let events = events.clone();
+            move |_| events.borrow_mut().push("error")
+        })
+        .read_bytes(&DataBlob::new("hello").unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(*events.borrow(), ["start", "error"]);
+}
+
+#[wasm_bindgen_test]
+async fn array_buffer_raw() {
+    let file = File::new(&[1, 2, 3], "bytes.bin", "").unwrap();
+    let buffer = file.array_buffer_raw().await.unwrap();
+    assert_eq!(js_sys::Uint8Array::new(&buffer).to_vec(), [1, 2, 3]);
+}
+
+#[wasm_bindgen_test]
+async fn when_idle() {
+    let reader = FileReader::new().unwrap();
+    reader.when_idle().await;
+
+    let blob = DataBlob::new("hello").unwrap();
+    let read = reader.read_as_string(&blob);
+    reader.when_idle().await;
+    assert_eq!(reader.ready_state(), ReadyState::Done);
+    assert_eq!(read.await.unwrap(), "hello");
+}
+
+#[wasm_bindgen_test]
+async fn empty_blobs_skip_the_browser() {
+    let reader = FileReader::new().unwrap();
+    let empty = DataBlob::new_with_type("", "text/plain").unwrap();
+    assert_eq!(reader.read_as_string(&empty).await.unwrap(), "");
+    assert_eq!(reader.read_as_binary_string(&empty).await.unwrap(), "");
+    assert!(reader
+        .read_as_array_buffer(&empty)
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        reader.read_as_data_url(&empty).await.unwrap(),
+        "data:text/plain;base64,"
+    );
+    assert_eq!(
+        reader
+            .read_as_data_url(&DataBlob::new("").unwrap())
+            .await
+            .unwrap(),
+        "data:application/octet-stream;base64,"
+    );
+    assert_eq!(reader.ready_state(), ReadyState::Empty);
+}
+
+#[wasm_bindgen_test]
+fn validate_file_list() {
+    let files = file_list(&[
+        raw_file("tiny", "a.png", "image/png"),
+        raw_file("much too big", "b.png", "image/png"),
+        raw_file("%PDF", "c.pdf", ""),
+        raw_file("text", "d.txt", "text/plain"),
+    ]);
+    let constraints = FileConstraints {
+        max_size: Some(4),
+        accept: Some("image/*, .PDF".to_string()),
+        max_files: Some(3),
+    };
+    assert_eq!(
+        files.validate(&constraints).unwrap_err(),
+        [
+            FileValidationError::TooManyFiles { count: 4, max: 3 },
+            FileValidationError::TooLarge {
+                name: "b.png".to_string(),
+                size: 12,
+                max: 4,
+            },
+            FileValidationError::WrongType {
+                name: "d.txt".to_string(),
+                mime_type: MimeType::TextPlain,
+            },
+        ]
+    );
+    assert!(files.validate(&FileConstraints::default()).is_ok());
+}
+
+#[wasm_bindgen_test]
+async fn read_head() {
+    let file = File::new(b"\x89PNG\r\n\x1a\nrest", "image.png", "").unwrap();
+    assert_eq!(file.read_head(8).await.unwrap(), b"\x89PNG\r\n\x1a\n");
+    assert_eq!(file.read_head(100).await.unwrap().len(), 12);
+    assert!(file.read_head(0).await.unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn from_raw_handles() {
+    let file = File::from(raw_file("hello", "hello.txt", "text/plain"));
+    assert_eq!(file.name(), "hello.txt");
+    assert_eq!(file.size(), 5);
+
+    let files = file_list(&[raw_file("a", "a.txt", ""), raw_file("b", "b.txt", "")]);
+    let files = FileList::from(files.into_raw());
+    assert_eq!(files.len(), 2);
+    assert_eq!(files.get(1).unwrap().name(), "b.txt");
+}
+
+#[wasm_bindgen_test]
+async fn abort_signal() {
+    let blob = DataBlob::new("hello").unwrap();
+
+    let controller = web_sys::AbortController::new().unwrap();
+    let read = FileReader::builder()
+        .signal(&controller.signal())
+        .read_string(&blob);
+    controller.abort();
+    match read.await {
+        Err(FileReadError::Aborted) => {}
+        other => panic!("expected `Aborted`, got {:?}", other),
+    }
+
+    // Already aborted before the read starts.
+    match FileReader::builder()
+        .signal(&controller.signal())
+        .read_bytes(&blob)
+        .await
+    {
+        Err(FileReadError::Aborted) => {}
+        other => panic!("expected `Aborted`, got {:?}", other),
+    }
+
+    let controller = web_sys::AbortController::new().unwrap();
+    let read = FileReader::builder()
+        .signal(&controller.signal())
+        .read_string(&blob);
+    assert_eq!(read.await.unwrap(), "hello");
+    controller.abort();
+}
+
+#[wasm_bindgen_test]
+fn common_directory_without_relative_paths() {
+    assert_eq!(file_list(&[]).common_directory(), None);
+
+    let files = file_list(&[raw_file("a", "a.txt", ""), raw_file("b", "b.txt", "")]);
+    assert_eq!(files.common_directory(), None);
+}
+
+#[wasm_bindgen_test]
+fn data_blob_len() {
+    let blob = DataBlob::new("hello").unwrap();
+    assert_eq!(blob.len(), 5);
+    assert!(!blob.is_empty());
+    assert!(DataBlob::new("").unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn file_with_name() {
+    let file = File::new(b"a,b", "my report.csv", "text/csv").unwrap();
+    let renamed = file.with_name("my_report.csv").unwrap();
+    assert_eq!(renamed.name(), "my_report.csv");
+    assert_eq!(renamed.mime_type(), MimeType::TextCsv);
+    assert_eq!(renamed.last_modified(), file.last_modified());
+    assert_eq!(renamed.text().await.unwrap(), "a,b");
+    assert_eq!(file.name(), "my report.csv");
+}
+
+#[cfg(feature = "zip")]
+#[wasm_bindgen_test]
+async fn unzip() {
+    use std::io::Write;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .add_directory("docs/", SimpleFileOptions::default())
+        .unwrap();
+    writer
+        .start_file("docs/hello.txt", SimpleFileOptions::default())
+        .unwrap();
+    writer.write_all(b"hello hello hello").unwrap();
+    writer
+        .start_file("empty", SimpleFileOptions::default())
+        .unwrap();
+    let archive = writer.finish().unwrap().into_inner();
+
+    let file = File::new(&archive, "archive.zip", "application/zip").unwrap();
+    assert_eq!(
+        file.unzip().await.unwrap(),
+        [
+            ("docs/hello.txt".to_string(), b"hello hello hello".to_vec()),
+            ("empty".to_string(), Vec::new()),
+        ]
+    );
+
+    let text = File::new(b"hello", "hello.zip", "application/zip").unwrap();
+    match text.unzip().await {
+        Err(FileReadError::Zip(_)) => {}
+        other => panic!("expected `Zip`, got {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn file_base64() {
+    let cases: [(&[u8], &str); 5] = [
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (&[0xfb, 0xff, 0x00, 0x3e], "+/8APg=="),
+    ];
+    for (bytes, base64) in &cases {
+        let file = File::new(bytes, "data.bin", "").unwrap();
+        assert_eq!(file.base64().await.unwrap(), *base64);
+    }
+}
+
+#[wasm_bindgen_test]
+fn file_list_dedup() {
+    let options = web_sys::FilePropertyBag::new();
+    options.set_last_modified(1000.0);
+    let dated = |contents: &str, name: &str| {
+        let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+        web_sys::File::new_with_str_sequence_and_options(&parts, name, &options).unwrap_throw()
+    };
+    let files = file_list(&[
+        dated("a", "a.txt"),
+        dated("b", "b.txt"),
+        dated("a", "a.txt"),
+        dated("aa", "a.txt"),
+    ]);
+    let unique: Vec<_> = files
+        .dedup()
+        .iter()
+        .map(|file| (file.name().to_string(), file.size()))
+        .collect();
+    assert_eq!(
+        unique,
+        [
+            ("a.txt".to_string(), 1),
+            ("b.txt".to_string(), 1),
+            ("a.txt".to_string(), 2),
+        ]
+    );
+}
+
+#[cfg(feature = "streams")]
+#[wasm_bindgen_test]
+async fn decompress() {
+    use gloo_file::CompressionFormat;
+
+    let gzip = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xcb, 0x48, 0xcd, 0xc9, 0xc9,
+        0x57, 0xc8, 0x40, 0x90, 0x00, 0x80, 0x88, 0xf9, 0xe5, 0x11, 0x00, 0x00, 0x00,
+    ];
+    let file = File::new(&gzip, "hello.txt.gz", "application/gzip").unwrap();
+    assert_eq!(
+        file.decompress(CompressionFormat::Gzip).await.unwrap(),
+        b"hello hello hello"
+    );
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    assert!(text.decompress(CompressionFormat::Gzip).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn watch_file_list() {
+    let input: HtmlInputElement = window()
+        .unwrap_throw()
+        .document()
+        .unwrap_throw()
+        .create_element("input")
+        .unwrap_throw()
+        .unchecked_into();
+    input.set_type("file");
+
+    let mut selections = FileList::watch(&input);
+    let transfer = web_sys::DataTransfer::new().unwrap_throw();
+    transfer
+        .items()
+        .add_with_file(&raw_file("hello", "hello.txt", "text/plain"))
+        .unwrap_throw();
+    input.set_files(transfer.files().as_ref());
+    input
+        .dispatch_event(&web_sys::Event::new("change").unwrap_throw())
+        .unwrap_throw();
+
+    let files = selections.next().await.unwrap();
+    assert_eq!(files.get(0).unwrap().name(), "hello.txt");
+}
+
+#[wasm_bindgen_test]
+fn file_list_sizes() {
+    let empty = file_list(&[]);
+    assert_eq!(empty.total_size(), 0);
+    assert!(empty.largest().is_none());
+
+    let files = file_list(&[
+        raw_file("a", "a.txt", ""),
+        raw_file("bbb", "b.txt", ""),
+        raw_file("ccc", "c.txt", ""),
+    ]);
+    assert_eq!(files.total_size(), 7);
+    assert_eq!(files.largest().unwrap().name(), "b.txt");
+}
+
+#[cfg(feature = "bytes")]
+#[wasm_bindgen_test]
+async fn file_bytes_buf() {
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    assert_eq!(
+        file.bytes_buf().await.unwrap(),
+        bytes::Bytes::from_static(b"hello")
+    );
+}
+
+#[wasm_bindgen_test]
+async fn read_tail() {
+    let file = File::new(b"header...trailer", "archive.bin", "").unwrap();
+    assert_eq!(file.read_tail(7).await.unwrap(), b"trailer");
+    assert_eq!(file.read_tail(100).await.unwrap(), b"header...trailer");
+    assert!(file.read_tail(0).await.unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn file_list_iter_text() {
+    let files = file_list(&[
+        raw_file("a,b", "a.csv", "text/csv"),
+        raw_file("hi", "b.txt", ""),
+    ]);
+    let texts: Vec<_> = files
+        .iter_text()
+        .map(|result| {
+            let (file, text) = result.unwrap();
+            (file.name().to_string(), text)
+        })
+        .collect()
+        .await;
+    assert_eq!(
+        texts,
+        [
+            ("a.csv".to_string(), "a,b".to_string()),
+            ("b.txt".to_string(), "hi".to_string()),
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+async fn file_reader_default() {
+    let reader = FileReader::default();
+    assert_eq!(reader.ready_state(), ReadyState::Empty);
+    let blob = DataBlob::new("hello").unwrap();
+    assert_eq!(reader.read_as_string(&blob).await.unwrap(), "hello");
+}
+
+#[wasm_bindgen_test]
+async fn file_content_eq() {
+    let a = File::new(b"same contents", "a.txt", "").unwrap();
+    let b = File::new(b"same contents", "b.txt", "text/plain").unwrap();
+    let c = File::new(b"same content!", "c.txt", "").unwrap();
+    let d = File::new(b"shorter", "d.txt", "").unwrap();
+    assert!(a.content_eq(&b).await.unwrap());
+    assert!(!a.content_eq(&c).await.unwrap());
+    assert!(!a.content_eq(&d).await.unwrap());
+}
+
+#[wasm_bindgen_test]
+async fn read_by_kind() {
+    let reader = FileReader::new().unwrap();
+    let blob = DataBlob::new_with_type("hi", "text/plain").unwrap();
+    assert_eq!(
+        reader.read(&blob, ReadKind::Text).await.unwrap(),
+        ReadResult::Text("hi".to_string())
+    );
+    assert_eq!(
+        reader.read(&blob, ReadKind::ArrayBuffer).await.unwrap(),
+        ReadResult::ArrayBuffer(b"hi".to_vec())
+    );
+    assert_eq!(
+        reader.read(&blob, ReadKind::DataUrl).await.unwrap(),
+        ReadResult::DataUrl("data:text/plain;base64,aGk=".to_string())
+    );
+    assert_eq!(
+        reader.read(&blob, ReadKind::BinaryString).await.unwrap(),
+        ReadResult::BinaryString("hi".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+async fn progress_stream() {
+    let reader = FileReader::new().unwrap();
+    let blob = DataBlob::new("hello").unwrap();
+    // The stream ends once the read does, without polling the future.
+    let (bytes, progress) = reader.read_as_array_buffer_with_progress(&blob);
+    let events: Vec<ProgressEvent> = progress.collect().await;
+    assert_eq!(bytes.await.unwrap(), b"hello");
+    assert!(events.iter().all(|event| event.total == Some(5)));
+
+    let (bytes, progress) = reader.read_as_array_buffer_with_progress(&DataBlob::new("").unwrap());
+    let (bytes, events) = futures::join!(bytes, progress.collect::<Vec<_>>());
+    assert!(bytes.unwrap().is_empty());
+    assert!(events.is_empty());
+}
+
+#[cfg(feature = "fs_access")]
+#[wasm_bindgen_test]
+async fn files_in_empty_directory() {
+    use wasm_bindgen_futures::JsFuture;
+
+    let storage = window().unwrap_throw().navigator().storage();
+    let root: web_sys::FileSystemDirectoryHandle = JsFuture::from(storage.get_directory())
+        .await
+        .unwrap()
+        .unchecked_into();
+    let options = web_sys::FileSystemGetDirectoryOptions::new();
+    options.set_create(true);
+    let dir: web_sys::FileSystemDirectoryHandle =
+        JsFuture::from(root.get_directory_handle_with_options("gloo-file-test", &options))
+            .await
+            .unwrap()
+            .unchecked_into();
+    // Subdirectories aren't listed.
+    JsFuture::from(dir.get_directory_handle_with_options("nested", &options))
+        .await
+        .unwrap();
+
+    assert!(gloo_file::files_in_directory(&dir)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[wasm_bindgen_test]
+async fn file_lines() {
+    let file = File::new(b"a,b\r\n1,2\n\nlast", "data.csv", "text/csv").unwrap();
+    let lines: Vec<String> = file.lines().map(Result::unwrap).collect().await;
+    assert_eq!(lines, ["a,b", "1,2", "", "last"]);
+
+    // The `é` straddles the first two 64 KB chunks.
+    let mut text = "a".repeat(64 * 1024 - 1);
+    text.push_str("é\nb\n");
+    let file = File::new(text.as_bytes(), "long.txt", "text/plain").unwrap();
+    let lines: Vec<String> = file.lines().map(Result::unwrap).collect().await;
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("aé"));
+    assert_eq!(lines[1], "b");
+}
+
+#[wasm_bindgen_test]
+async fn file_is_readable() {
+    assert!(
+        File::new(b"hello", "hello.txt", "")
+            .unwrap()
+            .is_readable()
+            .await
+    );
+    assert!(File::new(b"", "empty.txt", "").unwrap().is_readable().await);
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn file_metadata() {
+    let metadata = File::new(b"a,b", "report.csv", "text/csv")
+        .unwrap()
+        .metadata();
+    assert_eq!(metadata.name, "report.csv");
+    assert_eq!(metadata.size, 3);
+    assert_eq!(metadata.mime_type, "text/csv");
+    assert_eq!(metadata.relative_path, None);
+}
+
+#[wasm_bindgen_test]
+async fn file_list_read_within_budget() {
+    let files = file_list(&[
+        raw_file("aa", "a.txt", ""),
+        raw_file("bbb", "b.txt", ""),
+        raw_file("cccc", "c.txt", ""),
+        raw_file("d", "d.txt", ""),
+    ]);
+
+    let read = files.read_within_budget(6).await;
+    let read: Vec<_> = read
+        .iter()
+        .map(|(file, bytes)| (file.name(), bytes.as_slice()))
+        .collect();
+    assert_eq!(read, [("a.txt", &b"aa"[..]), ("b.txt", &b"bbb"[..])]);
+
+    assert!(files.read_within_budget(1).await.is_empty());
+    assert_eq!(files.read_within_budget(u64::MAX).await.len(), 4);
+}
+
+#[wasm_bindgen_test]
+fn file_list_from_files() {
+    let empty = FileList::from_files(Vec::new()).unwrap();
+    assert!(empty.is_empty());
+
+    let dropped = file_list(&[raw_file("a", "a.txt", "")]);
+    let picked = vec![
+        File::new(b"bb", "b.txt", "").unwrap(),
+        File::new(b"ccc", "c.txt", "").unwrap(),
+    ];
+    let files = FileList::from_files(dropped.iter().chain(picked)).unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files.as_raw().length(), 3);
+    assert_eq!(files.get(1).unwrap().name(), "b.txt");
+    assert!(files.get(3).is_none());
+    let names: Vec<_> = files.iter().map(|file| file.name().to_string()).collect();
+    assert_eq!(names, ["a.txt", "b.txt", "c.txt"]);
+}
+
+#[wasm_bindgen_test]
+async fn read_builder_retries() {
+    let blob = DataBlob::new("hello world").unwrap();
+    let contents = FileReader::builder()
+        .retries(3)
+        .read_string(&blob)
+        .await
+        .unwrap();
+    assert_eq!(contents, "hello world");
+
+    // An aborted read isn't transient, so it fails without being retried.
+    let controller = web_sys::AbortController::new().unwrap();
+    controller.abort();
+    let errors = Rc::new(RefCell::new(0));
+    let result = FileReader::builder()
+        .retries(3)
+        .signal(&controller.signal())
+        .on_error({
+            let errors = errors.clone();
+            move |_| *errors.borrow_mut() += 1
+        })
+        .read_bytes(&blob)
+        .await;
+    assert!(matches!(result, Err(FileReadError::Aborted)));
+    assert_eq!(*errors.borrow(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn file_chunks() {
+    let file = File::new(b"hello world", "hello.txt", "text/plain").unwrap();
+
+    let mut parts = Vec::new();
+    for chunk in file.chunks(4) {
+        let bytes = chunk.blob.array_buffer().await.unwrap();
+        parts.push((chunk.index, chunk.start, chunk.end, bytes));
+    }
+    assert_eq!(
+        parts,
+        [
+            (0, 0, 4, b"hell".to_vec()),
+            (1, 4, 8, b"o wo".to_vec()),
+            (2, 8, 11, b"rld".to_vec()),
+        ]
+    );
+
+    let empty = File::new(b"", "empty.txt", "").unwrap();
+    assert_eq!(empty.chunks(4).count(), 0);
+}
+
+#[cfg(feature = "digest")]
+#[wasm_bindgen_test]
+async fn file_list_dedup_by_content() {
+    let files = file_list(&[
+        raw_file("abc", "a.txt", ""),
+        raw_file("abd", "b.txt", ""),
+        raw_file("hello", "c.txt", ""),
+        raw_file("abc", "copy of a.txt", ""),
+        raw_file("hello", "d.txt", ""),
+    ]);
+    let names: Vec<_> = files
+        .dedup_by_content()
+        .await
+        .iter()
+        .map(|file| file.name().to_string())
+        .collect();
+    assert_eq!(names, ["a.txt", "b.txt", "c.txt"]);
+}
+
+#[cfg(feature = "csv")]
+#[wasm_bindgen_test]
+async fn csv_records() {
+    let csv = "name,quote\r\nAda,\"Hello, \"\"world\"\"\"\n\nGrace,\"two\nlines\"\n,\n";
+    let file = File::new(csv.as_bytes(), "people.csv", "text/csv").unwrap();
+
+    let records: Vec<_> = file.csv_records(true).map(Result::unwrap).collect().await;
+    assert_eq!(
+        records,
+        [
+            vec!["Ada", "Hello, \"world\""],
+            vec!["Grace", "two\nlines"],
+            vec!["", ""],
+        ]
+    );
+
+    let records: Vec<_> = file.csv_records(false).map(Result::unwrap).collect().await;
+    assert_eq!(records[0], ["name", "quote"]);
+    assert_eq!(records.len(), 4);
+
+    let unterminated = File::new(b"a,\"b", "open.csv", "text/csv").unwrap();
+    let records: Vec<_> = unterminated
+        .csv_records(false)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(records, [["a", "b"]]);
+}
+
+#[wasm_bindgen_test]
+async fn text_no_bom() {
+    let text_no_bom = |bytes: &[u8]| {
+        File::new(bytes, "export.txt", "text/plain")
+            .unwrap()
+            .text_no_bom()
+    };
+
+    assert_eq!(text_no_bom(b"\xef\xbb\xbfname").await.unwrap(), "name");
+    assert_eq!(text_no_bom(b"\xff\xfen\0a\0").await.unwrap(), "na");
+    assert_eq!(text_no_bom(b"\xfe\xff\0n\0a").await.unwrap(), "na");
+    assert_eq!(text_no_bom(b"\xfe\xff\0n\0").await.unwrap(), "n\u{fffd}");
+    assert_eq!(text_no_bom(b"no mark").await.unwrap(), "no mark");
+    assert_eq!(text_no_bom(b"").await.unwrap(), "");
+}
+
+#[wasm_bindgen_test]
+fn effective_mime() {
+    let declared = File::new(b"", "data.csv", "application/json").unwrap();
+    assert_eq!(declared.effective_mime(), MimeType::ApplicationJson);
+
+    let guessed = File::new(b"", "data.CSV", "").unwrap();
+    assert_eq!(guessed.effective_mime(), MimeType::TextCsv);
+
+    let unknown = File::new(b"", "README", "").unwrap();
+    assert_eq!(unknown.effective_mime(), MimeType::Unknown);
+}
+
+#[cfg(feature = "streams")]
+#[wasm_bindgen_test]
+async fn readable_stream() {
+    let file = File::new(b"hello world", "hello.txt", "text/plain").unwrap();
+    let stream = file.readable_stream();
+
+    let response = web_sys::Response::new_with_opt_readable_stream(Some(&stream)).unwrap();
+    let text = wasm_bindgen_futures::JsFuture::from(response.text().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(text, "hello world");
+}
+
+#[wasm_bindgen_test]
+async fn file_list_read_all_report() {
+    let files = file_list(&[raw_file("a", "a.txt", ""), raw_file("bb", "b.txt", "")]);
+
+    let report = files.read_all_report().await;
+    assert!(report.failed.is_empty());
+    let succeeded: Vec<_> = report
+        .succeeded
+        .iter()
+        .map(|(file, bytes)| (file.name(), bytes.as_slice()))
+        .collect();
+    assert_eq!(succeeded, [("a.txt", &b"a"[..]), ("b.txt", &b"bb"[..])]);
+}
+
+#[wasm_bindgen_test]
+fn same_object() {
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    let twin = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    assert!(file.same_object(&file.clone()));
+    assert!(file.same_object(&File::from_raw(file.as_raw().clone())));
+    assert!(!file.same_object(&twin));
+
+    let blob = DataBlob::new("hello").unwrap();
+    let other = DataBlob::new("hello").unwrap();
+    assert!(blob.same_object(&DataBlob::from_raw(blob.raw().clone())));
+    assert!(!blob.same_object(&other));
+}
+
+#[wasm_bindgen_test]
+async fn into_cursor() {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let file = File::new(b"hello world", "hello.txt", "text/plain").unwrap();
+    let mut cursor = file.into_cursor().await.unwrap();
+    cursor.seek(SeekFrom::Start(6)).unwrap();
+    let mut rest = String::new();
+    cursor.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "world");
+}
+
+#[wasm_bindgen_test]
+fn file_read_seek_outside_workers() {
+    // The tests run in a window, which has no `FileReaderSync`.
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    match gloo_file::FileReadSeek::new(&file) {
+        Err(gloo_file::Error::Unsupported(api)) => assert_eq!(api, "FileReaderSync"),
+        other => panic!("expected `Unsupported`, got {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test]
+fn file_list_partition_by_category() {
+    let files = file_list(&[
+        raw_file("", "a.png", "image/png"),
+        raw_file("", "b.txt", "text/plain"),
+        raw_file("", "c.jpg", "image/jpeg"),
+        raw_file("", "d", ""),
+    ]);
+    let groups = files.partition_by_category();
+    let names = |category| -> Vec<String> {
+        groups[&category]
+            .iter()
+            .map(|file| file.name().to_string())
+            .collect()
+    };
+
+    assert_eq!(groups.len(), 3);
+    assert_eq!(names(MimeCategory::Image), ["a.png", "c.jpg"]);
+    assert_eq!(names(MimeCategory::Text), ["b.txt"]);
+    assert_eq!(names(MimeCategory::Other), ["d"]);
+}
+
+#[cfg(feature = "exif")]
+#[wasm_bindgen_test]
+async fn exif_orientation() {
+    // The start of a JPEG: an APP0 segment, then an APP1 segment whose
+    // big-endian EXIF data has a single IFD entry, orientation 6.
+    let mut jpeg = vec![0xff, 0xd8];
+    jpeg.extend_from_slice(&[0xff, 0xe0, 0x00, 0x04, 0x00, 0x00]);
+    jpeg.extend_from_slice(&[0xff, 0xe1, 0x00, 0x22]);
+    jpeg.extend_from_slice(b"Exif\0\0MM\0\x2a\0\0\0\x08");
+    jpeg.extend_from_slice(&[0x00, 0x01]);
+    jpeg.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+    jpeg.extend_from_slice(&[0x00, 0x06, 0x00, 0x00]);
+    jpeg.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+    jpeg.extend_from_slice(&[0xff, 0xda]);
+
+    let photo = File::new(&jpeg, "photo.jpg", "image/jpeg").unwrap();
+    assert_eq!(photo.exif_orientation().await.unwrap(), Some(6));
+
+    let plain = File::new(b"\xff\xd8\xff\xda", "plain.jpg", "image/jpeg").unwrap();
+    assert_eq!(plain.exif_orientation().await.unwrap(), None);
+
+    let text = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    assert_eq!(text.exif_orientation().await.unwrap(), None);
+}
+
+#[wasm_bindgen_test]
+async fn file_cursor() {
+    let file = File::new(b"hello world", "hello.txt", "text/plain").unwrap();
+    let mut cursor = file.cursor();
+
+    assert_eq!(cursor.read(5).await.unwrap(), b"hello");
+    assert_eq!(cursor.position(), 5);
+    assert_eq!(cursor.read(4).await.unwrap(), b" wor");
+
+    // Reads stop short at the end of the file.
+    assert_eq!(cursor.read(10).await.unwrap(), b"ld");
+    assert!(cursor.is_at_end());
+    assert!(cursor.read(10).await.unwrap().is_empty());
+
+    cursor.seek(6);
+    assert_eq!(cursor.read(5).await.unwrap(), b"world");
+    cursor.seek(100);
+    assert!(cursor.read(1).await.unwrap().is_empty());
+    assert_eq!(cursor.position(), 100);
+}
+
+#[wasm_bindgen_test]
+async fn is_probably_text() {
+    let is_probably_text = |bytes: &[u8]| File::new(bytes, "file", "").unwrap().is_probably_text();
+
+    assert!(is_probably_text(b"hello\tworld\r\n").await.unwrap());
+    assert!(is_probably_text("caf\u{e9} \u{1f600}".as_bytes())
+        .await
+        .unwrap());
+    assert!(is_probably_text(b"").await.unwrap());
+    // A character cut off by the 8 KB limit is fine.
+    let mut long = vec![b'a'; 8 * 1024 - 1];
+    long.extend_from_slice("\u{e9}".as_bytes());
+    assert!(is_probably_text(&long).await.unwrap());
+
+    assert!(!is_probably_text(b"PK\x03\x04\0\0").await.unwrap());
+    assert!(!is_probably_text(b"\x89\x01\x02\x03\xfe\xff").await.unwrap());
+}
+
+#[wasm_bindgen_test]
+fn add_to_data_transfer() {
+    let transfer = web_sys::DataTransfer::new().unwrap();
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    gloo_file::add_to_data_transfer(&transfer, &file).unwrap();
+
+    let files = FileList::from_raw(transfer.files().unwrap());
+    assert_eq!(files.len(), 1);
+    assert_eq!(files.get(0).unwrap().name(), "hello.txt");
+}
+
+#[wasm_bindgen_test]
+async fn read_ranges() {
+    let file = File::new(b"hello world", "hello.txt", "text/plain").unwrap();
+
+    let ranges = file
+        .read_ranges(&[(6, 11), (0, 5), (3, 8), (9, 100), (20, 30)])
+        .await
+        .unwrap();
+    assert_eq!(ranges, [&b"world"[..], b"hello", b"lo wo", b"ld", b""]);
+    assert!(file.read_ranges(&[]).await.unwrap().is_empty());
+}
+
+#[wasm_bindgen_test]
+fn fingerprint() {
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    let fingerprint = file.fingerprint();
+    assert_eq!(fingerprint.len(), 16);
+    assert!(fingerprint.bytes().all(|byte| byte.is_ascii_hexdigit()));
+    assert_eq!(file.clone().fingerprint(), fingerprint);
+
+    let renamed = file.with_name("other.txt").unwrap();
+    assert_ne!(renamed.fingerprint(), fingerprint);
+    assert_eq!(
+        renamed.with_name("hello.txt").unwrap().fingerprint(),
+        fingerprint
+    );
+}
+
+#[wasm_bindgen_test]
+async fn file_list_read_all_bounded() {
+    let files = file_list(&[
+        raw_file("a", "a.txt", ""),
+        raw_file("bb", "b.txt", ""),
+        raw_file("ccc", "c.txt", ""),
+    ]);
+
+    let mut read: Vec<_> = files
+        .read_all_bounded(2)
+        .map(Result::unwrap)
+        .map(|(file, bytes)| (file.name().to_string(), bytes))
+        .collect()
+        .await;
+    read.sort();
+    assert_eq!(
+        read,
+        [
+            ("a.txt".to_string(), b"a".to_vec()),
+            ("b.txt".to_string(), b"bb".to_vec()),
+            ("c.txt".to_string(), b"ccc".to_vec()),
+        ]
+    );
+}
+
+#[cfg(feature = "promise")]
+#[wasm_bindgen_test]
+async fn read_promises() {
+    use wasm_bindgen_futures::JsFuture;
+
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    let text = JsFuture::from(file.text_promise()).await.unwrap();
+    assert_eq!(text, "hello");
+
+    let bytes = JsFuture::from(file.bytes_promise()).await.unwrap();
+    let bytes: js_sys::Uint8Array = bytes.dyn_into().unwrap();
+    assert_eq!(bytes.to_vec(), b"hello");
+}
+
+#[wasm_bindgen_test]
+async fn read_until() {
+    let file = File::new(b"name\0rest of the file", "data.bin", "").unwrap();
+    assert_eq!(file.read_until(0, 100).await.unwrap(), b"name\0");
+    assert_eq!(file.read_until(0, 3).await.unwrap(), b"nam");
+    assert_eq!(
+        file.read_until(b'!', 100).await.unwrap(),
+        b"name\0rest of the file"
+    );
+
+    // The delimiter is found past the first chunk.
+    let mut long = vec![b'a'; 10 * 1024];
+    long.push(b'\n');
+    long.extend_from_slice(b"more");
+    let file = File::new(&long, "long.txt", "").unwrap();
+    let line = file.read_until(b'\n', usize::MAX).await.unwrap();
+    assert_eq!(line.len(), 10 * 1024 + 1);
+    assert!(line.ends_with(b"a\n"));
+}
+
+#[cfg(all(feature = "debug-leak-tracking", debug_assertions))]
+#[wasm_bindgen_test]
+fn live_object_urls() {
+    use gloo_file::debug::live_object_urls;
+
+    let before = live_object_urls();
+    let blob = DataBlob::new("hello").unwrap();
+    let url = ObjectUrl::new(&blob);
+    let mut set = ObjectUrlSet::new();
+    set.insert(&blob);
+    assert_eq!(live_object_urls(), before + 2);
+
+    drop(url);
+    set.revoke_all();
+    assert_eq!(live_object_urls(), before);
+
+    std::mem::forget(ObjectUrl::new(&blob));
+    assert_eq!(live_object_urls(), before + 1);
+}
+
+#[wasm_bindgen_test]
+fn file_list_names() {
+    assert!(file_list(&[]).names().is_empty());
+
+    let files = file_list(&[raw_file("", "b.txt", ""), raw_file("", "a.txt", "")]);
+    assert_eq!(files.names(), ["b.txt", "a.txt"]);
+}
+
+#[wasm_bindgen_test]
+async fn data_url_with_type() {
+    let file = File::new(b"\x89PNG", "image.txt", "text/plain").unwrap();
+    assert_eq!(
+        file.data_url_with_type("image/png").await.unwrap(),
+        "data:image/png;base64,iVBORw=="
+    );
+
+    let empty = File::new(b"", "empty", "").unwrap();
+    assert_eq!(
+        empty.data_url_with_type("text/plain").await.unwrap(),
+        "data:text/plain;base64,"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn slice_as_file() {
+    let file = File::new(b"hello world", "hello.txt", "text/plain").unwrap();
+
+    let part = file.slice_as_file(6, 100, "hello.txt.part1").unwrap();
+    assert_eq!(part.name(), "hello.txt.part1");
+    assert_eq!(part.raw_type(), "text/plain");
+    assert_eq!(part.last_modified(), file.last_modified());
+    assert_eq!(part.bytes().await.unwrap(), b"world");
+
+    let empty = file.slice_as_file(8, 2, "empty").unwrap();
+    assert_eq!(empty.size(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn file_reader_pool() {
+    let pool = FileReaderPool::new(2);
+    pool.join().await;
+
+    let blobs: Vec<_> = (0..5)
+        .map(|i| DataBlob::new(&i.to_string()).unwrap())
+        .collect();
+    let reads = future::join_all(blobs.iter().map(|blob| pool.read_as_string(blob)));
+    let joined = Rc::new(RefCell::new(false));
+    let join = {
+        let joined = joined.clone();
+        pool.join().map(move |()| *joined.borrow_mut() = true)
+    };
+
+    let (contents, ()) = future::join(reads, join).await;
+    assert!(*joined.borrow());
+    let contents: Vec<_> = contents.into_iter().map(Result::unwrap).collect();
+    assert_eq!(contents, ["0", "1", "2", "3", "4"]);
+
+    // A read dropped while waiting for a reader doesn't hold up the pool.
+    let first = pool.read_as_array_buffer(&blobs[0]);
+    let second = pool.read_as_array_buffer(&blobs[1]);
+    drop(pool.read_as_data_url(&blobs[2]));
+    assert_eq!(first.await.unwrap(), b"0");
+    assert_eq!(second.await.unwrap(), b"1");
+    pool.join().await;
+}
+
+#[wasm_bindgen_test]
+async fn array_buffer_transferable() {
+    let file = File::new(b"hello", "hello.txt", "text/plain").unwrap();
+    let buffer = file.array_buffer_transferable().await.unwrap();
+    assert_eq!(buffer.byte_length(), 5);
+
+    let channel = web_sys::MessageChannel::new().unwrap_throw();
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    let onmessage = wasm_bindgen::closure::Closure::once(move |event: web_sys::MessageEvent| {
+        sender.send(event.data()).unwrap();
+    });
+    channel
+        .port2()
+        .set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+    channel
+        .port1()
+        .post_message_with_transferable(&buffer, &js_sys::Array::of1(&buffer))
+        .unwrap_throw();
+    // Transferring detaches the buffer on the sending side.
+    assert_eq!(buffer.byte_length(), 0);
+
+    let received: js_sys::ArrayBuffer = receiver.await.unwrap().unchecked_into();
+    assert_eq!(js_sys::Uint8Array::new(&received).to_vec(), b"hello");
+    channel.port2().close();
+}
+
+#[wasm_bindgen_test]
+fn file_list_enumerate() {
+    fn keyed(files: &FileList) -> impl Iterator<Item = (usize, File)> {
+        files.enumerate()
+    }
+
+    let files = file_list(&[raw_file("", "b.txt", ""), raw_file("", "a.txt", "")]);
+    let keyed: Vec<_> = keyed(&files)
+        .map(|(index, file)| (index, file.name().to_string()))
+        .collect();
+    assert_eq!(keyed, [(0, "b.txt".to_string()), (1, "a.txt".to_string())]);
+}
+
+#[wasm_bindgen_test]
+async fn read_decrypted() {
+    let file = File::new(b"\x29\x24\x2d\x2d\x2e", "hello.enc", "").unwrap();
+    let xor = |bytes: &[u8]| -> Result<Vec<u8>, String> {
+        Ok(bytes.iter().map(|byte| byte ^ 0x41).collect())
+    };
+    assert_eq!(file.read_decrypted(xor).await.unwrap(), b"hello");
+
+    let rejected = file.read_decrypted(|_| Err("bad key")).await.unwrap_err();
+    assert!(matches!(rejected, FileReadError::Decrypt(_)));
+    assert_eq!(
+        rejected.to_string(),
+        "the file could not be decrypted: bad key"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn bytes_with_stats() {
+    let contents = vec![7; 64 * 1024];
+    let file = File::new(&contents[..], "data.bin", "").unwrap();
+
+    let (bytes, stats) = file.bytes_with_stats().await.unwrap();
+    assert_eq!(bytes, contents);
+    assert_eq!(stats.bytes, 64 * 1024);
+    assert!(stats.elapsed_ms >= 0.0);
+    if stats.elapsed_ms > 0.0 {
+        let expected = stats.bytes as f64 * 1000.0 / stats.elapsed_ms;
+        assert!((stats.throughput_bytes_per_sec - expected).abs() < 1.0);
+    } else {
+        assert_eq!(stats.throughput_bytes_per_sec, 0.0);
+    }
+}
+
+#[wasm_bindgen_test]
+fn file_new_with_last_modified() {
+    let file = File::new_with_last_modified(b"hello", "hello.txt", "text/plain", 1e12).unwrap();
+    assert_eq!(file.last_modified(), 1e12);
+    assert_eq!(file.raw_type(), "text/plain");
+    assert_eq!(file.size(), 5);
+
+    let before = js_sys::Date::now();
+    let now = File::new(b"", "now.txt", "").unwrap();
+    assert!(now.last_modified() >= before.floor());
+}
+
+#[wasm_bindgen_test]
+async fn file_reader_pool_dropped_waiter() {
+    let pool = FileReaderPool::new(1);
+    let blob = DataBlob::new("hello").unwrap();
+
+    let mut first = Box::pin(pool.read_as_string(&blob));
+    assert!(futures::poll!(&mut first).is_pending());
+    let mut queued = Box::pin(pool.read_as_string(&blob));
+    assert!(futures::poll!(&mut queued).is_pending());
+
+    // Finishing the first read hands its reader to the queued one, which is
+    // dropped before it can take it.
+    assert_eq!(first.await.unwrap(), "hello");
+    drop(queued);
+
+    assert_eq!(pool.read_as_string(&blob).await.unwrap(), "hello");
+    pool.join().await;
+}
@@ -6,4 +6,5 @@
 // Re-exports of toolkit crates.
 pub use gloo_console_timer as console_timer;
 pub use gloo_events as events;
+pub use gloo_file as file;
 pub use gloo_timers as timers;